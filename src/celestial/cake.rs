@@ -0,0 +1,56 @@
+use uuid::Uuid;
+use serde::{Serialize, Deserialize};
+use super::Position2D;
+
+/// 하나의 도메인/프로젝트를 나타내는 케이크 구조
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cake {
+    pub id: String,
+    pub title: String,
+    pub center: Position2D,     // 캔버스에서의 케이크 중심
+    pub size_scale: f32,
+    pub color_theme: usize,     // 0: 기본, 1: 따뜻한톤, 2: 차가운톤, 3: 자연톤
+    pub max_layer: usize,       // 동적 레이어 관리 (0부터 시작)
+}
+
+/// 관계가 케이크 내부 연결인지, 케이크 간 연결인지
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CakeScope {
+    IntraCake,  // 같은 케이크 안의 두 노드
+    InterCake,  // 서로 다른 케이크(또는 케이크 밖)의 노드
+}
+
+impl Cake {
+    pub fn new(title: String, center: Position2D) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            title,
+            center,
+            size_scale: 1.0,
+            color_theme: 0,
+            max_layer: 0,  // 기본 1레이어
+        }
+    }
+
+    pub fn with_theme(mut self, color_theme: usize) -> Self {
+        self.color_theme = color_theme;
+        self
+    }
+
+    /// 레이어 확장 (줄어들지는 않음)
+    pub fn expand_to_layer(&mut self, layer: usize) {
+        if layer > self.max_layer {
+            self.max_layer = layer;
+        }
+    }
+
+    /// 층에 놓는 노드의 케이크 중심에서의 거리 (위층일수록 안쪽, 그릴 때 size_scale을 곱함)
+    pub fn node_radius(layer: usize) -> f32 {
+        80.0 * (0.8_f32.powi(layer as i32)).max(0.3)
+    }
+
+    /// 케이크의 층 개수
+    pub fn layer_count(&self) -> usize {
+        self.max_layer + 1
+    }
+}
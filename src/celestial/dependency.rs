@@ -0,0 +1,131 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use super::{Direction, EnforcementMode, Graph, GraphError, GraphResult, Node, NodeType, Relation, RelationType, TraversalFilter};
+
+// Dependency 관계 A → B 는 "A가 B에 의존한다"(B를 먼저 해야 한다)는 뜻이다.
+impl Graph {
+    fn dependency_filter() -> TraversalFilter {
+        TraversalFilter::new(Direction::Outgoing).with_types([RelationType::Dependency])
+    }
+
+    /// 새 Dependency 관계가 순환을 만드는지 검사 (모드에 따라 거부/경고)
+    pub(crate) fn check_dependency(&mut self, relation: &Relation) -> GraphResult<()> {
+        if relation.relation_type != RelationType::Dependency || self.dependency_mode() == EnforcementMode::Off {
+            return Ok(());
+        }
+
+        // target에서 source로 돌아오는 의존 경로가 있으면 순환
+        let Some(path) = self.shortest_path(&relation.target_id, &relation.source_id, &Self::dependency_filter()) else {
+            return Ok(());
+        };
+        let mut cycle = vec![relation.source_id.clone()];
+        cycle.extend(path.node_ids);
+
+        self.enforce(self.dependency_mode(), GraphError::DependencyCycle(cycle))
+    }
+
+    /// 의존성 순환 하나 찾기 (처음 노드가 끝에 다시 나옴)
+    pub fn find_dependency_cycle(&self) -> Option<Vec<String>> {
+        let mut finished = HashSet::new();
+        for start in self.sorted_node_ids() {
+            if finished.contains(start) {
+                continue;
+            }
+            if let Some(cycle) = self.dependency_cycle_from(start, &mut finished) {
+                return Some(cycle);
+            }
+        }
+        None
+    }
+
+    // 깊이 우선 탐색 (의존 사슬이 길어도 스택이 넘치지 않도록 직접 스택을 쌓는다)
+    fn dependency_cycle_from<'a>(&'a self, start: &'a str, finished: &mut HashSet<&'a str>) -> Option<Vec<String>> {
+        // 지금 경로 위의 노드와 아직 따라가지 않은 다음 노드들
+        let mut path: Vec<(&'a str, std::vec::IntoIter<&'a str>)> = vec![(start, self.dependency_targets(start))];
+        while let Some((node_id, targets)) = path.last_mut() {
+            let node_id = *node_id;
+            let Some(next_id) = targets.next() else {
+                finished.insert(node_id);
+                path.pop();
+                continue;
+            };
+            if let Some(position) = path.iter().position(|(id, _)| *id == next_id) {
+                let mut cycle: Vec<String> = path[position..].iter().map(|(id, _)| id.to_string()).collect();
+                cycle.push(next_id.to_string());
+                return Some(cycle);
+            }
+            if !finished.contains(next_id) {
+                path.push((next_id, self.dependency_targets(next_id)));
+            }
+        }
+        None
+    }
+
+    fn dependency_targets(&self, node_id: &str) -> std::vec::IntoIter<&str> {
+        self.edges(node_id, &Self::dependency_filter())
+            .into_iter()
+            .map(|(_, next_id)| next_id)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// 의존성을 먼저 해야 하는 순서로 정렬한 노드들 (node_type이 주어지면 그 타입만)
+    pub fn dependency_order(&self, node_type: Option<NodeType>) -> GraphResult<Vec<&Node>> {
+        // 남은 의존 개수 (나가는 Dependency 관계 수)
+        let mut remaining: HashMap<&str, usize> = self.get_nodes()
+            .map(|n| (n.id.as_str(), self.outgoing_relations_of_type(&n.id, &RelationType::Dependency).count()))
+            .collect();
+
+        // 같은 단계에서는 먼저 만든 노드가 먼저 (결과가 매번 같도록)
+        let rank = |id: &str| self.get_node(id).map(|n| (n.created_at, n.id.clone()));
+        let mut ready: BTreeSet<_> = remaining.iter()
+            .filter(|(_, count)| **count == 0)
+            .filter_map(|(id, _)| rank(id))
+            .collect();
+
+        let mut order = Vec::new();
+        while let Some(key) = ready.pop_first() {
+            let node_id = key.1.as_str();
+            if let Some(node) = self.get_node(node_id) {
+                order.push(node);
+            }
+            for dependent in self.incoming_relations_of_type(node_id, &RelationType::Dependency) {
+                if let Some(count) = remaining.get_mut(dependent.source_id.as_str()) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.extend(rank(&dependent.source_id));
+                    }
+                }
+            }
+        }
+
+        if order.len() < self.node_count() {
+            if let Some(cycle) = self.find_dependency_cycle() {
+                return Err(GraphError::DependencyCycle(cycle));
+            }
+            // 순환이 없는데 남았다면 없는 노드에 의존하는 관계 때문
+            if let Some(relation) = self.get_relations().find(|r| {
+                r.relation_type == RelationType::Dependency && self.get_node(&r.target_id).is_none()
+            }) {
+                return Err(GraphError::DanglingRelation {
+                    relation_id: relation.id.clone(),
+                    node_id: relation.target_id.clone(),
+                });
+            }
+        }
+
+        Ok(order.into_iter()
+            .filter(|n| node_type.as_ref().is_none_or(|t| n.node_type == *t))
+            .collect())
+    }
+
+    /// Task 노드들을 먼저 해야 하는 순서로
+    pub fn task_order(&self) -> GraphResult<Vec<&Node>> {
+        self.dependency_order(Some(NodeType::Task))
+    }
+
+    fn sorted_node_ids(&self) -> Vec<&str> {
+        let mut nodes: Vec<&Node> = self.get_nodes().collect();
+        nodes.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        nodes.into_iter().map(|n| n.id.as_str()).collect()
+    }
+}
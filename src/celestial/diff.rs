@@ -0,0 +1,141 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use super::{Direction, Graph, GraphError, GraphResult, Node, RelationType, TraversalFilter};
+
+/// 버전 사이에서 비교하는 노드 필드
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NodeField {
+    Title,
+    Description,
+    NodeType,
+    Layer,
+    Color,
+    Size,
+    Opacity,
+    Scale,
+    /// 사용자 정의 속성 (키)
+    Property(String),
+}
+
+impl NodeField {
+    pub fn display_name(&self) -> &str {
+        match self {
+            NodeField::Title => "Title",
+            NodeField::Description => "Description",
+            NodeField::NodeType => "Type",
+            NodeField::Layer => "Layer",
+            NodeField::Color => "Color",
+            NodeField::Size => "Size",
+            NodeField::Opacity => "Opacity",
+            NodeField::Scale => "Scale",
+            NodeField::Property(key) => key,
+        }
+    }
+}
+
+/// 필드 하나의 변경 (값은 화면 표시용 문자열, 없으면 None)
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: NodeField,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} → {}",
+            self.field.display_name(),
+            self.before.as_deref().unwrap_or("(none)"),
+            self.after.as_deref().unwrap_or("(none)")
+        )
+    }
+}
+
+/// 두 노드 버전 사이의 차이
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeDiff {
+    pub from_id: String,
+    pub to_id: String,
+    pub changes: Vec<FieldChange>,
+}
+
+impl NodeDiff {
+    /// 두 노드의 필드와 속성을 비교 (ID, 위치, 시간 정보는 비교하지 않음)
+    pub fn between(from: &Node, to: &Node) -> Self {
+        let fields = [
+            (NodeField::Title, Some(from.title.clone()), Some(to.title.clone())),
+            (NodeField::Description, from.description.clone(), to.description.clone()),
+            (NodeField::NodeType, Some(from.node_type.display_name().to_string()), Some(to.node_type.display_name().to_string())),
+            (NodeField::Layer, Some(from.layer.to_string()), Some(to.layer.to_string())),
+            (NodeField::Color, from.custom_color_rgba.map(format_rgba), to.custom_color_rgba.map(format_rgba)),
+            (NodeField::Size, from.custom_size.map(|s| s.to_string()), to.custom_size.map(|s| s.to_string())),
+            (NodeField::Opacity, Some(from.opacity.to_string()), Some(to.opacity.to_string())),
+            (NodeField::Scale, Some(from.scale.to_string()), Some(to.scale.to_string())),
+        ];
+
+        let keys: BTreeSet<&String> = from.properties.keys().chain(to.properties.keys()).collect();
+        let properties = keys.into_iter().map(|key| (
+            NodeField::Property(key.clone()),
+            from.properties.get(key).map(|v| v.to_string()),
+            to.properties.get(key).map(|v| v.to_string()),
+        ));
+
+        let changes = fields.into_iter()
+            .chain(properties)
+            .filter(|(_, before, after)| before != after)
+            .map(|(field, before, after)| FieldChange { field, before, after })
+            .collect();
+
+        Self {
+            from_id: from.id.clone(),
+            to_id: to.id.clone(),
+            changes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn change(&self, field: &NodeField) -> Option<&FieldChange> {
+        self.changes.iter().find(|c| c.field == *field)
+    }
+}
+
+fn format_rgba([r, g, b, a]: [u8; 4]) -> String {
+    format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+}
+
+impl Graph {
+    /// 같은 진화 트리에 속한 두 버전 비교
+    pub fn diff_versions(&self, from_id: &str, to_id: &str) -> GraphResult<NodeDiff> {
+        let from = self.require_node(from_id)?;
+        let to = self.require_node(to_id)?;
+        if self.common_evolution_ancestor(from_id, to_id).is_none() {
+            return Err(GraphError::UnrelatedVersions {
+                from_id: from_id.to_string(),
+                to_id: to_id.to_string(),
+            });
+        }
+        Ok(NodeDiff::between(from, to))
+    }
+
+    /// 최초 버전부터 이 노드까지 각 단계의 변경 기록 (오래된 순)
+    pub fn revision_log(&self, node_id: &str) -> GraphResult<Vec<NodeDiff>> {
+        self.require_node(node_id)?;
+        let Some(root) = self.evolution_root(node_id) else {
+            return Ok(Vec::new());
+        };
+
+        let filter = TraversalFilter::new(Direction::Outgoing).with_types([RelationType::Evolution]);
+        let path = self.shortest_path(&root.id, node_id, &filter)
+            .map(|p| p.node_ids)
+            .unwrap_or_default();
+
+        Ok(path.windows(2)
+            .filter_map(|pair| Some(NodeDiff::between(self.get_node(&pair[0])?, self.get_node(&pair[1])?)))
+            .collect())
+    }
+}
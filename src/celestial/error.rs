@@ -0,0 +1,134 @@
+use std::fmt;
+use super::{NodeType, RelationType};
+
+/// 그래프 편집이 거부된 이유
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphError {
+    /// 존재하지 않는 노드 ID
+    UnknownNode(String),
+    /// 존재하지 않는 관계 ID
+    UnknownRelation(String),
+    /// 존재하지 않는 케이크 ID
+    UnknownCake(String),
+    /// 부모 타입이 자식 타입을 가질 수 없음
+    InvalidHierarchy {
+        parent_type: NodeType,
+        child_type: NodeType,
+    },
+    /// 같은 방향, 같은 타입의 관계가 이미 있음
+    DuplicateRelation {
+        source_id: String,
+        target_id: String,
+        relation_type: RelationType,
+    },
+    /// 노드가 자기 자신과 연결됨
+    SelfLoop(String),
+    /// 자식에게 이미 다른 부모가 있음
+    ParentConflict {
+        child_id: String,
+        existing_parent_id: String,
+    },
+    /// 부모가 자식의 자손이라 계층이 순환함
+    HierarchyCycle {
+        parent_id: String,
+        child_id: String,
+    },
+    /// Dependency 관계가 순환함 (순환을 이루는 노드 ID들, 처음 노드가 끝에 다시 나옴)
+    DependencyCycle(Vec<String>),
+    /// 관계가 그래프에 없는 노드를 가리킴
+    DanglingRelation {
+        relation_id: String,
+        node_id: String,
+    },
+    /// 등록되지 않았거나 잘못된 노드 타입 이름
+    InvalidNodeType(String),
+    /// 등록되지 않았거나 잘못된 관계 타입 이름
+    InvalidRelationType(String),
+    /// 관계 타입이 이 노드 타입 조합을 허용하지 않음
+    RelationNotAllowed {
+        relation_type: RelationType,
+        source_type: NodeType,
+        target_type: NodeType,
+    },
+    /// 스키마 설정상 이 층에 둘 수 없는 노드 타입
+    LayerNotAllowed {
+        node_type: NodeType,
+        layer: usize,
+    },
+    /// 스키마 파일을 읽을 수 없거나 잘못됨
+    InvalidSchema(String),
+    /// 두 노드가 같은 진화 트리에 속하지 않음
+    UnrelatedVersions {
+        from_id: String,
+        to_id: String,
+    },
+}
+
+pub type GraphResult<T> = Result<T, GraphError>;
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::UnknownNode(id) => write!(f, "unknown node '{}'", id),
+            GraphError::UnknownRelation(id) => write!(f, "unknown relation '{}'", id),
+            GraphError::UnknownCake(id) => write!(f, "unknown cake '{}'", id),
+            GraphError::InvalidHierarchy { parent_type, child_type } => write!(
+                f,
+                "{} cannot be a parent of {}",
+                parent_type.display_name(),
+                child_type.display_name()
+            ),
+            GraphError::DuplicateRelation { source_id, target_id, relation_type } => write!(
+                f,
+                "{} relation from '{}' to '{}' already exists",
+                relation_type.display_name(),
+                source_id,
+                target_id
+            ),
+            GraphError::SelfLoop(id) => write!(f, "node '{}' cannot be related to itself", id),
+            GraphError::ParentConflict { child_id, existing_parent_id } => write!(
+                f,
+                "node '{}' already has parent '{}'",
+                child_id,
+                existing_parent_id
+            ),
+            GraphError::HierarchyCycle { parent_id, child_id } => write!(
+                f,
+                "'{}' is a descendant of '{}', so it cannot be its parent",
+                parent_id,
+                child_id
+            ),
+            GraphError::DependencyCycle(cycle) => write!(f, "dependency cycle: {}", cycle.join(" -> ")),
+            GraphError::DanglingRelation { relation_id, node_id } => write!(
+                f,
+                "relation '{}' points to missing node '{}'",
+                relation_id,
+                node_id
+            ),
+            GraphError::InvalidNodeType(name) => write!(f, "invalid node type '{}'", name),
+            GraphError::InvalidRelationType(name) => write!(f, "invalid relation type '{}'", name),
+            GraphError::RelationNotAllowed { relation_type, source_type, target_type } => write!(
+                f,
+                "{} relation is not allowed from {} to {}",
+                relation_type.display_name(),
+                source_type.display_name(),
+                target_type.display_name()
+            ),
+            GraphError::LayerNotAllowed { node_type, layer } => write!(
+                f,
+                "{} is not allowed on layer {}",
+                node_type.display_name(),
+                layer + 1
+            ),
+            GraphError::InvalidSchema(reason) => write!(f, "invalid schema: {}", reason),
+            GraphError::UnrelatedVersions { from_id, to_id } => write!(
+                f,
+                "'{}' and '{}' are not versions of the same node",
+                from_id,
+                to_id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use super::Graph;
+
+/// 그래프가 바뀔 때 구독자에게 쌓이는 이벤트
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphEvent {
+    NodeAdded(String),
+    /// 노드 내용이 바뀜 (get_node_mut으로 고친 경우도 포함)
+    NodeUpdated(String),
+    NodeRemoved(String),
+    RelationAdded(String),
+    /// 관계의 속성이 바뀜
+    RelationUpdated(String),
+    RelationRemoved {
+        id: String,
+        source_id: String,
+        target_id: String,
+    },
+    /// 케이크가 추가되었거나 바뀜
+    CakeUpdated(String),
+    CakeRemoved(String),
+    /// 타입 정의, 층, 규칙 적용 방식이 바뀜
+    RulesChanged,
+    /// 그래프 전체가 바뀜 (실행 취소, 계층 복구 등). 구독자는 모두 다시 읽어야 한다
+    Reset,
+}
+
+/// 이벤트와 그 이벤트가 (마지막으로) 일어난 시각
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimedEvent {
+    pub at: DateTime<Utc>,
+    pub event: GraphEvent,
+}
+
+/// 이벤트 구독 ID
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+/// 구독자별 이벤트 대기열 (구독자가 없으면 아무것도 쌓지 않음)
+#[derive(Clone, Default)]
+pub(super) struct EventQueues {
+    next_id: u64,
+    queues: BTreeMap<SubscriptionId, Vec<TimedEvent>>,
+}
+
+impl EventQueues {
+    pub(super) fn emit(&mut self, event: GraphEvent) {
+        if self.queues.is_empty() {
+            return;
+        }
+        let at = Utc::now();
+        for queue in self.queues.values_mut() {
+            // 드래그처럼 같은 노드를 연달아 고치면 한 번만 알림 (시각은 마지막 것으로)
+            match queue.last_mut() {
+                Some(last) if last.event == event => last.at = at,
+                _ => queue.push(TimedEvent {
+                    at,
+                    event: event.clone(),
+                }),
+            }
+        }
+    }
+}
+
+impl Graph {
+    /// 변경 이벤트 구독 시작 (이후의 변경부터 쌓인다)
+    pub fn subscribe(&mut self) -> SubscriptionId {
+        let id = SubscriptionId(self.events.next_id);
+        self.events.next_id += 1;
+        self.events.queues.insert(id, Vec::new());
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) {
+        self.events.queues.remove(&id);
+    }
+
+    /// 쌓인 이벤트를 일어난 순서대로 꺼내기
+    pub fn take_events(&mut self, id: SubscriptionId) -> Vec<GraphEvent> {
+        self.take_timed_events(id).into_iter().map(|timed| timed.event).collect()
+    }
+
+    /// 쌓인 이벤트를 일어난 시각과 함께 꺼내기
+    pub fn take_timed_events(&mut self, id: SubscriptionId) -> Vec<TimedEvent> {
+        self.events.queues.get_mut(&id).map(std::mem::take).unwrap_or_default()
+    }
+
+    /// 그래프 내용을 통째로 바꾸고 이전 내용 반환 (구독은 유지하고 Reset 알림)
+    pub fn replace_with(&mut self, other: Graph) -> Graph {
+        self.record_all(Some(&other));
+        let mut previous = std::mem::replace(self, other);
+        std::mem::swap(&mut self.events, &mut previous.events);
+        std::mem::swap(&mut self.recorders, &mut previous.recorders);
+        previous.events = EventQueues::default();
+        previous.recorders.clear();
+        self.events.emit(GraphEvent::Reset);
+        previous
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashSet;
+use super::{Direction, Graph, Node, RelationType, TraversalFilter};
+
+// Evolution 관계 A → B 는 "B가 A에서 발전했다"는 뜻이다.
+// 한 노드가 여러 방향으로 발전할 수 있으므로 진화 기록은 체인이 아니라 트리다.
+// 여러 갈래가 있으면 항상 먼저 만든 노드(created_at, 같으면 ID 순)를 먼저 둔다.
+impl Graph {
+    fn evolution_filter(direction: Direction) -> TraversalFilter {
+        TraversalFilter::new(direction).with_types([RelationType::Evolution])
+    }
+
+    /// 바로 다음 버전들 (먼저 만든 순)
+    pub fn evolution_successors(&self, node_id: &str) -> Vec<&Node> {
+        self.sorted_by_creation(self.neighbors(node_id, &Self::evolution_filter(Direction::Outgoing)))
+    }
+
+    /// 바로 이전 버전들 (보통 하나, 먼저 만든 순)
+    pub fn evolution_predecessors(&self, node_id: &str) -> Vec<&Node> {
+        self.sorted_by_creation(self.neighbors(node_id, &Self::evolution_filter(Direction::Incoming)))
+    }
+
+    /// 이전 버전들 (가까운 순, 시작 노드 제외)
+    pub fn evolution_ancestors(&self, node_id: &str) -> Vec<&Node> {
+        self.bfs(node_id, Self::evolution_filter(Direction::Incoming))
+            .skip(1)
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// 이후 버전들 (가까운 순, 시작 노드 제외)
+    pub fn evolution_descendants(&self, node_id: &str) -> Vec<&Node> {
+        self.bfs(node_id, Self::evolution_filter(Direction::Outgoing))
+            .skip(1)
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// 진화 트리의 최초 버전 (이전 버전이 여럿이면 먼저 만든 쪽을 따라감)
+    pub fn evolution_root(&self, node_id: &str) -> Option<&Node> {
+        let mut current = self.get_node(node_id)?;
+        let mut visited = HashSet::from([current.id.as_str()]);
+        while let Some(previous) = self.evolution_predecessors(&current.id).into_iter().next() {
+            if !visited.insert(previous.id.as_str()) {
+                break; // 손상된 파일의 순환 방지
+            }
+            current = previous;
+        }
+        Some(current)
+    }
+
+    /// 더 발전하지 않은 최신 버전들 (먼저 만든 순, 자기 자신이 최신이면 자신)
+    pub fn latest_versions(&self, node_id: &str) -> Vec<&Node> {
+        let leaves = self.bfs(node_id, Self::evolution_filter(Direction::Outgoing))
+            .map(|(node, _)| node)
+            .filter(|node| self.evolution_successors(&node.id).is_empty())
+            .collect();
+        self.sorted_by_creation(leaves)
+    }
+
+    /// 시작 노드에서 각 최신 버전까지의 갈래들
+    pub fn evolution_branches(&self, node_id: &str) -> Vec<Vec<&Node>> {
+        let mut branches = Vec::new();
+        if let Some(node) = self.get_node(node_id) {
+            self.collect_branches(vec![node], &mut branches);
+        }
+        branches
+    }
+
+    fn collect_branches<'a>(&'a self, path: Vec<&'a Node>, branches: &mut Vec<Vec<&'a Node>>) {
+        let Some(last) = path.last() else {
+            return;
+        };
+        let next: Vec<&Node> = self.evolution_successors(&last.id)
+            .into_iter()
+            .filter(|n| !path.iter().any(|p| p.id == n.id))
+            .collect();
+        if next.is_empty() {
+            branches.push(path);
+            return;
+        }
+        for node in next {
+            let mut branch = path.clone();
+            branch.push(node);
+            self.collect_branches(branch, branches);
+        }
+    }
+
+    /// 두 버전의 가장 가까운 공통 조상 (한쪽이 다른 쪽의 조상이면 그 노드)
+    pub fn common_evolution_ancestor(&self, a_id: &str, b_id: &str) -> Option<&Node> {
+        let a_lineage: HashSet<&str> = self.bfs(a_id, Self::evolution_filter(Direction::Incoming))
+            .map(|(node, _)| node.id.as_str())
+            .collect();
+        self.bfs(b_id, Self::evolution_filter(Direction::Incoming))
+            .map(|(node, _)| node)
+            .find(|node| a_lineage.contains(node.id.as_str()))
+    }
+
+    fn sorted_by_creation<'a>(&self, mut nodes: Vec<&'a Node>) -> Vec<&'a Node> {
+        nodes.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        nodes
+    }
+}
@@ -1,11 +1,97 @@
 use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
-use super::{Node, Relation, NodeType, Position2D, Position3D, RelationType};
+use super::{Node, Relation, NodeType, Position2D, RelationType, Cake, CakeScope, GraphError, GraphResult, EnforcementMode, LayerDef, NodeTypeRegistry, RelationTypeRegistry};
+use super::index::AdjacencyIndex;
+use super::tags::TagIndex;
+use super::events::{EventQueues, GraphEvent};
+use super::journal::GraphRules;
+use super::history::ChangeRecorder;
+use super::guard::{CakeMut, NodeMut, RelationMut};
 
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "GraphData")]
 pub struct Graph {
+    pub(super) nodes: HashMap<String, Node>,
+    #[serde(serialize_with = "serialize_by_creation")]
+    pub(super) relations: Vec<Relation>,
+    #[serde(default)]
+    pub(super) cakes: Vec<Cake>,
+    #[serde(default)]
+    hierarchy_mode: EnforcementMode,
+    #[serde(default)]
+    dependency_mode: EnforcementMode,
+    #[serde(default)]
+    pub(super) node_types: NodeTypeRegistry,
+    #[serde(default)]
+    pub(super) relation_types: RelationTypeRegistry,
+    #[serde(default)]
+    pub(super) layers: Vec<LayerDef>,
+    #[serde(skip)]
+    pub(super) index: AdjacencyIndex,
+    #[serde(skip)]
+    pub(super) tag_index: TagIndex,
+    #[serde(skip)]
+    pub(super) events: EventQueues,
+    /// 실행 취소 기록 중인 편집들 (바깥 묶음부터)
+    #[serde(skip)]
+    pub(super) recorders: Vec<ChangeRecorder>,
+    /// Warn 모드에서 허용된 규칙 위반 (`take_warnings`로 꺼냄)
+    #[serde(skip)]
+    pub(super) warnings: Vec<GraphError>,
+}
+
+/// 파일에 저장되는 그래프 데이터 (불러온 뒤 인덱스를 다시 만든다)
+#[derive(Deserialize)]
+struct GraphData {
     nodes: HashMap<String, Node>,
     relations: Vec<Relation>,
+    #[serde(default)]
+    cakes: Vec<Cake>,
+    #[serde(default)]
+    hierarchy_mode: EnforcementMode,
+    #[serde(default)]
+    dependency_mode: EnforcementMode,
+    #[serde(default)]
+    node_types: NodeTypeRegistry,
+    #[serde(default)]
+    relation_types: RelationTypeRegistry,
+    #[serde(default)]
+    layers: Vec<LayerDef>,
+}
+
+/// swap_remove와 되돌리기로 벡터 안 순서는 섞이므로 저장할 때는 만든 순서로
+fn serialize_by_creation<S: serde::Serializer>(relations: &[Relation], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut sorted: Vec<&Relation> = relations.iter().collect();
+    sorted.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+    serializer.collect_seq(sorted)
+}
+
+impl From<GraphData> for Graph {
+    fn from(data: GraphData) -> Self {
+        let index = AdjacencyIndex::build(&data.relations);
+        let tag_index = TagIndex::build(data.nodes.values());
+        Self {
+            nodes: data.nodes,
+            relations: data.relations,
+            cakes: data.cakes,
+            hierarchy_mode: data.hierarchy_mode,
+            dependency_mode: data.dependency_mode,
+            node_types: data.node_types,
+            relation_types: data.relation_types,
+            layers: data.layers,
+            index,
+            tag_index,
+            events: EventQueues::default(),
+            recorders: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Graph {
@@ -13,30 +99,134 @@ impl Graph {
         Self {
             nodes: HashMap::new(),
             relations: Vec::new(),
+            cakes: Vec::new(),
+            hierarchy_mode: EnforcementMode::default(),
+            dependency_mode: EnforcementMode::default(),
+            node_types: NodeTypeRegistry::default(),
+            relation_types: RelationTypeRegistry::default(),
+            layers: Vec::new(),
+            index: AdjacencyIndex::default(),
+            tag_index: TagIndex::default(),
+            events: EventQueues::default(),
+            recorders: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
-    pub fn create_node(&mut self, title: String, node_type: NodeType, position: Position2D) -> String {
-        let node = Node::new(title, node_type, position);
+    /// 계층 규칙 적용 방식
+    pub fn hierarchy_mode(&self) -> EnforcementMode {
+        self.hierarchy_mode
+    }
+
+    pub fn set_hierarchy_mode(&mut self, mode: EnforcementMode) {
+        self.record_rules();
+        self.hierarchy_mode = mode;
+        self.events.emit(GraphEvent::RulesChanged);
+    }
+
+    /// 의존성 순환 검사 방식
+    pub fn dependency_mode(&self) -> EnforcementMode {
+        self.dependency_mode
+    }
+
+    pub fn set_dependency_mode(&mut self, mode: EnforcementMode) {
+        self.record_rules();
+        self.dependency_mode = mode;
+        self.events.emit(GraphEvent::RulesChanged);
+    }
+
+    /// 타입 정의, 층, 규칙 적용 방식 (저널 기록용)
+    pub(super) fn rules(&self) -> GraphRules {
+        GraphRules {
+            node_types: self.node_types.clone(),
+            relation_types: self.relation_types.clone(),
+            layers: self.layers.clone(),
+            hierarchy_mode: self.hierarchy_mode,
+            dependency_mode: self.dependency_mode,
+        }
+    }
+
+    pub(super) fn set_rules(&mut self, rules: GraphRules) {
+        self.record_rules();
+        self.node_types = rules.node_types;
+        self.relation_types = rules.relation_types;
+        self.layers = rules.layers;
+        self.hierarchy_mode = rules.hierarchy_mode;
+        self.dependency_mode = rules.dependency_mode;
+        self.events.emit(GraphEvent::RulesChanged);
+    }
+
+    pub fn create_node(&mut self, title: String, node_type: NodeType, position: Position2D) -> GraphResult<String> {
+        let node = self.typed_node(title, node_type, position);
+        self.add_node(node)
+    }
+
+    // 그래프의 타입 정의(기본 층, 색, 크기)를 따르는 새 노드
+    fn typed_node(&self, title: String, node_type: NodeType, position: Position2D) -> Node {
+        let mut node = Node::new(title, node_type, position);
+        self.apply_type_defaults(&mut node, true);
+        node
+    }
+
+    /// 이미 만들어진 노드 추가 (층 규칙 검사, 같은 ID가 있으면 교체)
+    pub fn add_node(&mut self, node: Node) -> GraphResult<String> {
+        self.check_layer(&node.node_type, node.layer)?;
+        Ok(self.put_node(node))
+    }
+
+    /// 노드를 검사 없이 넣거나 같은 ID의 노드를 교체 (태그 인덱스도 갱신, 저널 재생용)
+    pub(super) fn put_node(&mut self, node: Node) -> String {
         let id = node.id.clone();
+        self.record_node(&id);
+        let event = match self.detach_node(&id) {
+            Some(_) => GraphEvent::NodeUpdated(id.clone()),
+            None => GraphEvent::NodeAdded(id.clone()),
+        };
+        self.tag_index.insert(&node);
         self.nodes.insert(id.clone(), node);
+        self.events.emit(event);
         id
     }
 
+    // 노드만 빼내기 (관계/계층 정리는 호출하는 쪽에서)
+    pub(super) fn take_node(&mut self, id: &str) -> Option<Node> {
+        let node = self.detach_node(id)?;
+        self.events.emit(GraphEvent::NodeRemoved(id.to_string()));
+        Some(node)
+    }
+
+    fn detach_node(&mut self, id: &str) -> Option<Node> {
+        self.record_node(id);
+        let node = self.nodes.remove(id)?;
+        self.tag_index.remove(&node);
+        Some(node)
+    }
+
     /// 3D 위치로 노드 생성
-    pub fn create_node_3d(&mut self, title: String, node_type: NodeType, layer: usize, radius: f32, angle: f32) -> String {
-        let node = Node::new_3d(title, node_type, layer, radius, angle);
-        let id = node.id.clone();
-        self.nodes.insert(id.clone(), node);
-        id
+    pub fn create_node_3d(&mut self, title: String, node_type: NodeType, layer: usize, radius: f32, angle: f32) -> GraphResult<String> {
+        let mut node = Node::new_3d(title, node_type, layer, radius, angle);
+        self.apply_type_defaults(&mut node, false);
+        self.add_node(node)
+    }
+
+    /// 케이크에 속한 노드 생성 (케이크 레이어의 반지름/각도 위치)
+    pub fn create_cake_node(&mut self, title: String, node_type: NodeType, cake_id: &str, layer: usize, radius: f32, angle: f32) -> GraphResult<String> {
+        self.check_layer(&node_type, layer)?;
+        self.expand_cake(cake_id, layer)?;
+
+        let mut node = Node::new_3d(title, node_type, layer, radius, angle)
+            .with_cake(cake_id.to_string());
+        self.apply_type_defaults(&mut node, false);
+        Ok(self.put_node(node))
     }
 
     pub fn get_node(&self, id: &str) -> Option<&Node> {
         self.nodes.get(id)
     }
 
-    pub fn get_node_mut(&mut self, id: &str) -> Option<&mut Node> {
-        self.nodes.get_mut(id)
+    /// 노드 수정용 (고친 경우에만 놓을 때 NodeUpdated 이벤트가 생긴다)
+    pub fn get_node_mut(&mut self, id: &str) -> Option<NodeMut<'_>> {
+        NodeMut::new(self, id)
     }
 
     pub fn get_nodes(&self) -> impl Iterator<Item = &Node> {
@@ -47,97 +237,380 @@ impl Graph {
         self.relations.iter()
     }
 
-    pub fn create_child_node(&mut self, title: String, node_type: NodeType, parent_id: &str, position: Position2D) -> Option<String> {
-        let parent = self.get_node(parent_id)?;
+    pub fn get_relation(&self, id: &str) -> Option<&Relation> {
+        self.index.position(id).map(|position| &self.relations[position])
+    }
+
+    /// 관계 수정용 (ID와 끝점은 인덱스와 맞아야 하므로 바꾸지 말 것)
+    pub(super) fn get_relation_mut(&mut self, id: &str) -> GraphResult<RelationMut<'_>> {
+        let position = self.index.position(id)
+            .ok_or_else(|| GraphError::UnknownRelation(id.to_string()))?;
+        Ok(RelationMut::new(self, position))
+    }
+
+    /// 노드에서 나가는 관계들 (추가된 순서)
+    pub fn outgoing_relations(&self, node_id: &str) -> impl Iterator<Item = &Relation> {
+        self.index.outgoing(node_id).filter_map(|id| self.get_relation(id))
+    }
+
+    /// 노드로 들어오는 관계들 (추가된 순서)
+    pub fn incoming_relations(&self, node_id: &str) -> impl Iterator<Item = &Relation> {
+        self.index.incoming(node_id).filter_map(|id| self.get_relation(id))
+    }
+
+    /// 노드에서 나가는 특정 타입의 관계들 (추가된 순서)
+    pub fn outgoing_relations_of_type<'a>(&'a self, node_id: &str, relation_type: &RelationType) -> impl Iterator<Item = &'a Relation> {
+        self.index.outgoing_of_type(node_id, relation_type)
+            .iter()
+            .filter_map(|id| self.get_relation(id))
+    }
+
+    /// 노드로 들어오는 특정 타입의 관계들 (추가된 순서)
+    pub fn incoming_relations_of_type<'a>(&'a self, node_id: &str, relation_type: &RelationType) -> impl Iterator<Item = &'a Relation> {
+        self.index.incoming_of_type(node_id, relation_type)
+            .iter()
+            .filter_map(|id| self.get_relation(id))
+    }
+
+    /// 노드에 연결된 모든 관계들 (나가는 관계 먼저)
+    pub fn node_relations(&self, node_id: &str) -> impl Iterator<Item = &Relation> {
+        self.outgoing_relations(node_id).chain(self.incoming_relations(node_id))
+    }
+
+    pub fn create_child_node(&mut self, title: String, node_type: NodeType, parent_id: &str, position: Position2D) -> GraphResult<String> {
+        self.require_node(parent_id)?;
         let new_pos = position;
         
-        let node = Node::new(title, node_type, new_pos)
+        let node = self.typed_node(title, node_type, new_pos)
             .with_parent(parent_id.to_string());
         let id = node.id.clone();
         
-        self.nodes.insert(id.clone(), node);
-        self.add_relation(parent_id, &id, RelationType::Hierarchy);
+        self.add_node(node)?;
+        if let Err(e) = self.add_relation(parent_id, &id, RelationType::Hierarchy) {
+            // 계층 규칙에 막히면 노드도 만들지 않음
+            self.take_node(&id);
+            return Err(e);
+        }
 
-        Some(id)
+        Ok(id)
     }
 
-    pub fn evolve_node(&mut self, base_node_id: &str, title: String, position: Option<Position2D>) -> Option<String> {
-        let base_node = self.get_node(base_node_id)?;
+    pub fn evolve_node(&mut self, base_node_id: &str, title: String, position: Option<Position2D>) -> GraphResult<String> {
+        let base_node = self.require_node(base_node_id)?;
         let pos = position.unwrap_or_else(|| Position2D::new(
             base_node.position.x + 50.0,
             base_node.position.y + 50.0
         ));
         
-        let node = Node::new(title, base_node.node_type.clone(), pos);
+        let node = self.typed_node(title, base_node.node_type.clone(), pos);
         let id = node.id.clone();
         
-        self.nodes.insert(id.clone(), node);
-        self.add_relation(base_node_id, &id, RelationType::Evolution);
+        self.add_node(node)?;
+        if let Err(e) = self.add_relation(base_node_id, &id, RelationType::Evolution) {
+            // 관계 규칙에 막히면 노드도 만들지 않음
+            self.take_node(&id);
+            return Err(e);
+        }
 
-        Some(id)
+        Ok(id)
     }
 
-    pub fn add_relation(&mut self, source_id: &str, target_id: &str, relation_type: RelationType) {
-        let relation = Relation::new(source_id.to_string(), target_id.to_string(), relation_type);
-        self.relations.push(relation);
+    /// 기존 노드의 내용(설명, 타입, 층, 스타일)을 그대로 복사해서 진화
+    pub fn evolve_node_with_content(&mut self, base_node_id: &str, title: Option<String>, position: Option<Position2D>) -> GraphResult<String> {
+        let base_node = self.require_node(base_node_id)?;
+        let pos = position.unwrap_or_else(|| Position2D::new(
+            base_node.position.x + 50.0,
+            base_node.position.y + 50.0
+        ));
+
+        let now = chrono::Utc::now();
+        let mut node = base_node.clone();
+        node.id = uuid::Uuid::new_v4().to_string();
+        node.title = title.unwrap_or_else(|| base_node.title.clone());
+        node.position = pos;
+        node.parent_id = None;
+        node.children_ids.clear();
+        node.created_at = now;
+        node.updated_at = now;
+        node.selected = false;
+        let id = node.id.clone();
+
+        self.add_node(node)?;
+        if let Err(e) = self.add_relation(base_node_id, &id, RelationType::Evolution) {
+            self.take_node(&id);
+            return Err(e);
+        }
+
+        Ok(id)
+    }
+
+    /// 관계 추가 (생성된 관계 ID 반환)
+    pub fn add_relation(&mut self, source_id: &str, target_id: &str, relation_type: RelationType) -> GraphResult<String> {
+        self.add_relation_entry(Relation::new(source_id.to_string(), target_id.to_string(), relation_type))
+    }
+
+    /// 이미 만들어진 관계 추가 (라벨, 가중치 등을 지정한 경우)
+    pub fn add_relation_entry(&mut self, relation: Relation) -> GraphResult<String> {
+        self.require_node(&relation.source_id)?;
+        self.require_node(&relation.target_id)?;
+
+        if relation.source_id == relation.target_id {
+            return Err(GraphError::SelfLoop(relation.source_id));
+        }
+
+        self.check_relation_type(&relation)?;
+
+        // 방향이 없는 관계 타입은 반대 방향도 같은 관계로 본다
+        let duplicate = self.outgoing_relations_of_type(&relation.source_id, &relation.relation_type)
+            .any(|r| r.target_id == relation.target_id) ||
+            (!self.relation_types.is_directed(&relation.relation_type) &&
+                self.incoming_relations_of_type(&relation.source_id, &relation.relation_type)
+                    .any(|r| r.source_id == relation.target_id));
+        if duplicate {
+            return Err(GraphError::DuplicateRelation {
+                source_id: relation.source_id,
+                target_id: relation.target_id,
+                relation_type: relation.relation_type,
+            });
+        }
+
+        self.check_hierarchy(&relation)?;
+        self.check_hierarchy_structure(&relation)?;
+        self.check_dependency(&relation)?;
+
+        let id = relation.id.clone();
+        self.link_hierarchy(&relation);
+        self.insert_relation(relation);
+        Ok(id)
     }
 
     /// create_relation은 add_relation의 별칭 (호환성을 위해)
-    pub fn create_relation(&mut self, source_id: &str, target_id: &str, relation_type: RelationType) {
-        self.add_relation(source_id, target_id, relation_type);
+    pub fn create_relation(&mut self, source_id: &str, target_id: &str, relation_type: RelationType) -> GraphResult<String> {
+        self.add_relation(source_id, target_id, relation_type)
     }
 
     /// 관계 제거
-    pub fn remove_relation(&mut self, relation_id: &str) {
-        self.relations.retain(|r| r.id != relation_id);
+    pub fn remove_relation(&mut self, relation_id: &str) -> GraphResult<Relation> {
+        let relation = self.take_relation(relation_id)
+            .ok_or_else(|| GraphError::UnknownRelation(relation_id.to_string()))?;
+        self.unlink_hierarchy(&relation);
+        Ok(relation)
     }
 
-    /// 노드 제거
-    pub fn remove_node(&mut self, node_id: &str) {
-        self.nodes.remove(node_id);
-        // 해당 노드와 관련된 모든 관계도 제거
-        self.relations.retain(|r| r.source_id != node_id && r.target_id != node_id);
+    /// 관계를 그대로 넣거나 같은 ID의 관계를 교체 (검사와 계층 정리 없음, 저널 재생용)
+    pub(super) fn put_relation(&mut self, relation: Relation) {
+        self.record_relation(&relation.id);
+        if let Some(position) = self.index.position(&relation.id) {
+            let id = relation.id.clone();
+            // 끝점이 바뀌었을 수 있으므로 인덱스를 다시 만든다
+            self.relations[position] = relation;
+            self.rebuild_index();
+            self.events.emit(GraphEvent::RelationUpdated(id));
+        } else {
+            self.insert_relation(relation);
+        }
     }
 
-    /// 특정 노드의 자식들 가져오기
-    pub fn get_children(&self, parent_id: &str) -> Vec<&Node> {
-        self.relations.iter()
-            .filter(|r| r.source_id == parent_id && r.is_parent_child())
-            .filter_map(|r| self.get_node(&r.target_id))
-            .collect()
+    /// 관계만 빼내기 (계층 정리는 호출하는 쪽에서)
+    pub(super) fn take_relation(&mut self, relation_id: &str) -> Option<Relation> {
+        let position = self.index.position(relation_id)?;
+        self.record_relation(relation_id);
+        let relation = self.relations.swap_remove(position);
+        self.index.remove(&relation, position, &self.relations);
+        self.events.emit(GraphEvent::RelationRemoved {
+            id: relation.id.clone(),
+            source_id: relation.source_id.clone(),
+            target_id: relation.target_id.clone(),
+        });
+        Some(relation)
     }
 
-    /// 특정 노드의 부모 가져오기
-    pub fn get_parent(&self, child_id: &str) -> Option<&Node> {
-        self.relations.iter()
-            .find(|r| r.target_id == child_id && r.is_parent_child())
-            .and_then(|r| self.get_node(&r.source_id))
+    /// 관계 벡터와 인덱스에 함께 추가 (검사는 호출하는 쪽에서)
+    pub(super) fn insert_relation(&mut self, relation: Relation) {
+        self.record_relation(&relation.id);
+        self.index.insert(&relation, self.relations.len());
+        self.events.emit(GraphEvent::RelationAdded(relation.id.clone()));
+        self.relations.push(relation);
     }
 
-    /// 진화 체인 가져오기
-    pub fn get_evolution_chain(&self, node_id: &str) -> Vec<&Node> {
-        let mut chain = Vec::new();
-        let mut current_id = node_id;
-        
-        // 시작 노드 추가
-        if let Some(node) = self.get_node(current_id) {
-            chain.push(node);
+    /// relations를 직접 고친 뒤 인덱스 다시 만들기
+    pub(super) fn rebuild_index(&mut self) {
+        self.index = AdjacencyIndex::build(&self.relations);
+    }
+
+    /// 노드 제거 (자식들은 부모 없는 노드로 남음)
+    pub fn remove_node(&mut self, node_id: &str) -> GraphResult<Node> {
+        self.require_node(node_id)?;
+
+        // 해당 노드와 관련된 모든 관계도 제거 (계층 정보도 함께 정리)
+        let relation_ids: Vec<String> = self.node_relations(node_id)
+            .map(|r| r.id.clone())
+            .collect();
+        for relation_id in relation_ids {
+            self.remove_relation(&relation_id)?;
         }
-        
-        // 진화 체인 따라가기
-        while let Some(relation) = self.relations.iter()
-            .find(|r| r.source_id == current_id && r.relation_type == RelationType::Evolution) {
-            if let Some(node) = self.get_node(&relation.target_id) {
-                chain.push(node);
-                current_id = &relation.target_id;
-            } else {
-                break;
+
+        self.detach_from_parent(node_id);
+        let orphan_ids: Vec<String> = self.nodes.values()
+            .filter(|n| n.parent_id.as_deref() == Some(node_id))
+            .map(|n| n.id.clone())
+            .collect();
+        for orphan_id in orphan_ids {
+            self.detach_from_parent(&orphan_id);
+        }
+
+        self.take_node(node_id)
+            .ok_or_else(|| GraphError::UnknownNode(node_id.to_string()))
+    }
+
+    /// 노드가 없으면 UnknownNode 에러
+    pub(crate) fn require_node(&self, id: &str) -> GraphResult<&Node> {
+        self.get_node(id).ok_or_else(|| GraphError::UnknownNode(id.to_string()))
+    }
+
+    /// 진화 체인 가져오기 (갈래가 여럿이면 먼저 만든 버전을 따라감)
+    pub fn get_evolution_chain(&self, node_id: &str) -> Vec<&Node> {
+        let mut chain: Vec<&Node> = self.get_node(node_id).into_iter().collect();
+        while let Some(current) = chain.last() {
+            let next = self.evolution_successors(&current.id)
+                .into_iter()
+                .find(|n| !chain.iter().any(|c| c.id == n.id));
+            match next {
+                Some(node) => chain.push(node),
+                None => break,
             }
         }
-        
         chain
     }
 
+    /// 새 케이크 생성
+    pub fn create_cake(&mut self, title: String, center: Position2D) -> String {
+        self.add_cake(Cake::new(title, center))
+    }
+
+    /// 이미 만들어진 케이크 추가 (같은 ID가 있으면 교체)
+    pub fn add_cake(&mut self, cake: Cake) -> String {
+        let id = cake.id.clone();
+        self.record_cake(&id);
+        match self.cakes.iter_mut().find(|c| c.id == id) {
+            Some(existing) => *existing = cake,
+            None => self.cakes.push(cake),
+        }
+        self.events.emit(GraphEvent::CakeUpdated(id.clone()));
+        id
+    }
+
+    pub fn get_cake(&self, id: &str) -> Option<&Cake> {
+        self.cakes.iter().find(|c| c.id == id)
+    }
+
+    /// 케이크 수정용 (고친 경우에만 놓을 때 CakeUpdated 이벤트가 생긴다)
+    pub fn get_cake_mut(&mut self, id: &str) -> Option<CakeMut<'_>> {
+        CakeMut::new(self, id)
+    }
+
+    /// 케이크의 최대 레이어 확장 (이미 충분하면 그대로)
+    pub fn expand_cake(&mut self, cake_id: &str, layer: usize) -> GraphResult<()> {
+        let cake = self.get_cake(cake_id)
+            .ok_or_else(|| GraphError::UnknownCake(cake_id.to_string()))?;
+        if cake.max_layer < layer {
+            if let Some(mut cake) = self.get_cake_mut(cake_id) {
+                cake.expand_to_layer(layer);
+            }
+        }
+        Ok(())
+    }
+
+    /// 생성 순서대로 케이크들 반환
+    pub fn get_cakes(&self) -> impl Iterator<Item = &Cake> {
+        self.cakes.iter()
+    }
+
+    /// 케이크 제거 (소속 노드와 그 관계들도 함께 제거)
+    pub fn remove_cake(&mut self, cake_id: &str) -> GraphResult<Cake> {
+        let index = self.cakes.iter()
+            .position(|c| c.id == cake_id)
+            .ok_or_else(|| GraphError::UnknownCake(cake_id.to_string()))?;
+        let member_ids: Vec<String> = self.get_cake_nodes(cake_id)
+            .into_iter()
+            .map(|n| n.id.clone())
+            .collect();
+        for node_id in member_ids {
+            self.remove_node(&node_id)?;
+        }
+        self.record_cake(cake_id);
+        self.events.emit(GraphEvent::CakeRemoved(cake_id.to_string()));
+        Ok(self.cakes.remove(index))
+    }
+
+    /// 케이크만 빼내기 (소속 노드는 그대로, 저널 재생용)
+    pub(super) fn take_cake(&mut self, cake_id: &str) -> Option<Cake> {
+        let index = self.cakes.iter().position(|c| c.id == cake_id)?;
+        self.record_cake(cake_id);
+        self.events.emit(GraphEvent::CakeRemoved(cake_id.to_string()));
+        Some(self.cakes.remove(index))
+    }
+
+    /// 케이크에 속한 노드들
+    pub fn get_cake_nodes(&self, cake_id: &str) -> Vec<&Node> {
+        self.nodes.values()
+            .filter(|n| n.cake_id.as_deref() == Some(cake_id))
+            .collect()
+    }
+
+    /// 케이크의 특정 레이어에 있는 노드들
+    pub fn get_cake_layer_nodes(&self, cake_id: &str, layer: usize) -> Vec<&Node> {
+        self.nodes.values()
+            .filter(|n| n.cake_id.as_deref() == Some(cake_id) && n.is_in_layer(layer))
+            .collect()
+    }
+
+    /// 노드가 속한 케이크
+    pub fn get_node_cake(&self, node_id: &str) -> Option<&Cake> {
+        let cake_id = self.get_node(node_id)?.cake_id.as_deref()?;
+        self.get_cake(cake_id)
+    }
+
+    /// 노드를 다른 케이크로 이동 (레이어는 유지하고 대상 케이크를 확장)
+    pub fn move_node_to_cake(&mut self, node_id: &str, cake_id: &str) -> GraphResult<()> {
+        let layer = self.require_node(node_id)?.layer;
+        self.expand_cake(cake_id, layer)?;
+
+        if let Some(mut node) = self.get_node_mut(node_id) {
+            node.cake_id = Some(cake_id.to_string());
+            node.updated_at = chrono::Utc::now();
+        }
+        Ok(())
+    }
+
+    /// 관계가 케이크 내부 연결인지 케이크 간 연결인지 계산
+    pub fn relation_scope(&self, relation: &Relation) -> CakeScope {
+        let source_cake = self.get_node(&relation.source_id).and_then(|n| n.cake_id.as_ref());
+        let target_cake = self.get_node(&relation.target_id).and_then(|n| n.cake_id.as_ref());
+
+        match (source_cake, target_cake) {
+            (Some(a), Some(b)) if a == b => CakeScope::IntraCake,
+            _ => CakeScope::InterCake,
+        }
+    }
+
+    /// 서로 다른 케이크를 잇는 관계들
+    pub fn get_inter_cake_relations(&self) -> impl Iterator<Item = &Relation> {
+        self.relations.iter()
+            .filter(|r| self.relation_scope(r) == CakeScope::InterCake)
+    }
+
+    /// 케이크 개수
+    pub fn cake_count(&self) -> usize {
+        self.cakes.len()
+    }
+
+    /// 두 노드 사이에 (방향과 무관하게) 관계가 있는지 확인
+    pub fn are_connected(&self, a_id: &str, b_id: &str) -> bool {
+        self.outgoing_relations(a_id).any(|r| r.target_id == b_id) ||
+            self.outgoing_relations(b_id).any(|r| r.target_id == a_id)
+    }
+
     /// 노드 개수
     pub fn node_count(&self) -> usize {
         self.nodes.len()
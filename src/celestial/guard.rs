@@ -0,0 +1,149 @@
+use std::ops::{Deref, DerefMut};
+use super::{Cake, Graph, Node, Relation};
+use super::events::GraphEvent;
+
+/// 노드 수정용 핸들 (`Graph::get_node_mut`)
+///
+/// 실제로 고친 경우에만 놓을 때 NodeUpdated 이벤트를 내고 태그 인덱스를 맞춘다.
+/// 노드 ID는 바꾸지 말 것.
+pub struct NodeMut<'a> {
+    graph: &'a mut Graph,
+    id: String,
+    // 처음 고치기 직전의 내용 (읽기만 했으면 None)
+    before: Option<Node>,
+}
+
+impl<'a> NodeMut<'a> {
+    pub(super) fn new(graph: &'a mut Graph, id: &str) -> Option<Self> {
+        graph.nodes.contains_key(id).then(|| Self {
+            graph,
+            id: id.to_string(),
+            before: None,
+        })
+    }
+}
+
+impl Deref for NodeMut<'_> {
+    type Target = Node;
+
+    fn deref(&self) -> &Node {
+        &self.graph.nodes[&self.id]
+    }
+}
+
+impl DerefMut for NodeMut<'_> {
+    fn deref_mut(&mut self) -> &mut Node {
+        if self.before.is_none() {
+            self.graph.record_node(&self.id);
+            self.before = Some(self.graph.nodes[&self.id].clone());
+        }
+        self.graph.nodes.get_mut(&self.id).expect("node exists while borrowed")
+    }
+}
+
+impl Drop for NodeMut<'_> {
+    fn drop(&mut self) {
+        let Some(before) = self.before.take() else {
+            return;
+        };
+        let node = &self.graph.nodes[&self.id];
+        if node.tags != before.tags {
+            self.graph.tag_index.remove(&before);
+            self.graph.tag_index.insert(node);
+        }
+        self.graph.events.emit(GraphEvent::NodeUpdated(self.id.clone()));
+    }
+}
+
+/// 케이크 수정용 핸들 (`Graph::get_cake_mut`, 실제로 고친 경우에만 CakeUpdated 이벤트)
+pub struct CakeMut<'a> {
+    graph: &'a mut Graph,
+    index: usize,
+    changed: bool,
+}
+
+impl<'a> CakeMut<'a> {
+    pub(super) fn new(graph: &'a mut Graph, id: &str) -> Option<Self> {
+        let index = graph.cakes.iter().position(|c| c.id == id)?;
+        Some(Self {
+            graph,
+            index,
+            changed: false,
+        })
+    }
+}
+
+impl Deref for CakeMut<'_> {
+    type Target = Cake;
+
+    fn deref(&self) -> &Cake {
+        &self.graph.cakes[self.index]
+    }
+}
+
+impl DerefMut for CakeMut<'_> {
+    fn deref_mut(&mut self) -> &mut Cake {
+        if !self.changed {
+            let id = self.graph.cakes[self.index].id.clone();
+            self.graph.record_cake(&id);
+            self.changed = true;
+        }
+        &mut self.graph.cakes[self.index]
+    }
+}
+
+impl Drop for CakeMut<'_> {
+    fn drop(&mut self) {
+        if self.changed {
+            let id = self.graph.cakes[self.index].id.clone();
+            self.graph.events.emit(GraphEvent::CakeUpdated(id));
+        }
+    }
+}
+
+/// 관계 수정용 핸들 (실제로 고친 경우에만 RelationUpdated 이벤트)
+///
+/// ID와 끝점은 인덱스와 맞아야 하므로 바꾸지 말 것.
+pub(super) struct RelationMut<'a> {
+    graph: &'a mut Graph,
+    position: usize,
+    changed: bool,
+}
+
+impl<'a> RelationMut<'a> {
+    pub(super) fn new(graph: &'a mut Graph, position: usize) -> Self {
+        Self {
+            graph,
+            position,
+            changed: false,
+        }
+    }
+}
+
+impl Deref for RelationMut<'_> {
+    type Target = Relation;
+
+    fn deref(&self) -> &Relation {
+        &self.graph.relations[self.position]
+    }
+}
+
+impl DerefMut for RelationMut<'_> {
+    fn deref_mut(&mut self) -> &mut Relation {
+        if !self.changed {
+            let id = self.graph.relations[self.position].id.clone();
+            self.graph.record_relation(&id);
+            self.changed = true;
+        }
+        &mut self.graph.relations[self.position]
+    }
+}
+
+impl Drop for RelationMut<'_> {
+    fn drop(&mut self) {
+        if self.changed {
+            let id = self.graph.relations[self.position].id.clone();
+            self.graph.events.emit(GraphEvent::RelationUpdated(id));
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::{HashMap, HashSet};
+use super::{Graph, GraphError, GraphEvent, GraphResult, Node, Relation, RelationType};
+
+// 계층 구조는 세 곳에 표현된다:
+// 자식의 parent_id, 부모의 children_ids, 그리고 Hierarchy/Parent/Child 관계.
+// 관계를 추가/제거하는 모든 경로가 아래 함수들을 거쳐 세 표현을 함께 갱신한다.
+impl Graph {
+    /// 특정 노드의 자식들 가져오기
+    pub fn get_children(&self, parent_id: &str) -> Vec<&Node> {
+        self.get_node(parent_id)
+            .map(|parent| {
+                parent.children_ids.iter()
+                    .filter_map(|id| self.get_node(id))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 특정 노드의 부모 가져오기
+    pub fn get_parent(&self, child_id: &str) -> Option<&Node> {
+        let parent_id = self.get_node(child_id)?.parent_id.as_deref()?;
+        self.get_node(parent_id)
+    }
+
+    /// 부모 방향으로 올라가며 조상들 가져오기 (가까운 순)
+    pub fn get_ancestors(&self, node_id: &str) -> Vec<&Node> {
+        let mut ancestors = Vec::new();
+        let mut visited = HashSet::new();
+        visited.insert(node_id.to_string());
+
+        let mut current = self.get_parent(node_id);
+        while let Some(node) = current {
+            if !visited.insert(node.id.clone()) {
+                break; // 손상된 파일의 순환 방지
+            }
+            ancestors.push(node);
+            current = self.get_parent(&node.id);
+        }
+        ancestors
+    }
+
+    /// node_id가 ancestor_id의 자손인지 (또는 같은 노드인지) 확인
+    pub fn is_descendant_of(&self, node_id: &str, ancestor_id: &str) -> bool {
+        node_id == ancestor_id ||
+            self.get_ancestors(node_id).iter().any(|n| n.id == ancestor_id)
+    }
+
+    /// 부모 변경 (None이면 최상위 노드로 분리, 새 부모와 연결하지 못하면 원래 부모를 그대로 둠)
+    pub fn set_parent(&mut self, child_id: &str, parent_id: Option<&str>) -> GraphResult<()> {
+        self.require_node(child_id)?;
+        if let Some(parent_id) = parent_id {
+            self.require_node(parent_id)?;
+            if self.is_descendant_of(parent_id, child_id) {
+                return Err(GraphError::HierarchyCycle {
+                    parent_id: parent_id.to_string(),
+                    child_id: child_id.to_string(),
+                });
+            }
+            // 기존 부모를 떼기 전에 새 부모가 규칙상 가능한지 먼저 확인
+            self.check_hierarchy(&Relation::new(parent_id.to_string(), child_id.to_string(), RelationType::Hierarchy))?;
+        }
+
+        // 관계 타입 제한이나 의존성 검사는 기존 부모를 뗀 뒤에야 알 수 있으므로 실패하면 되돌린다
+        self.atomically(|graph| {
+            let old_relations: Vec<String> = graph.parent_relations(child_id)
+                .map(|r| r.id.clone())
+                .collect();
+            for relation_id in old_relations {
+                graph.remove_relation(&relation_id)?;
+            }
+            graph.detach_from_parent(child_id);
+
+            if let Some(parent_id) = parent_id {
+                graph.add_relation(parent_id, child_id, RelationType::Hierarchy)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// 새 계층 관계가 단일 부모/비순환 구조를 깨지 않는지 확인
+    pub(crate) fn check_hierarchy_structure(&self, relation: &Relation) -> GraphResult<()> {
+        let Some((parent_id, child_id)) = relation.parent_and_child() else {
+            return Ok(());
+        };
+
+        if let Some(existing) = self.get_node(child_id).and_then(|n| n.parent_id.as_deref()) {
+            if existing != parent_id {
+                return Err(GraphError::ParentConflict {
+                    child_id: child_id.to_string(),
+                    existing_parent_id: existing.to_string(),
+                });
+            }
+        }
+
+        if self.is_descendant_of(parent_id, child_id) {
+            return Err(GraphError::HierarchyCycle {
+                parent_id: parent_id.to_string(),
+                child_id: child_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// 계층 관계가 추가된 뒤 parent_id/children_ids 반영
+    pub(crate) fn link_hierarchy(&mut self, relation: &Relation) {
+        let Some((parent_id, child_id)) = relation.parent_and_child() else {
+            return;
+        };
+
+        if let Some(mut child) = self.get_node_mut(child_id) {
+            if child.parent_id.as_deref() != Some(parent_id) {
+                child.parent_id = Some(parent_id.to_string());
+                child.updated_at = chrono::Utc::now();
+            }
+        }
+        let has_child = self.get_node(parent_id)
+            .is_some_and(|p| p.children_ids.iter().any(|id| id == child_id));
+        if !has_child {
+            if let Some(mut parent) = self.get_node_mut(parent_id) {
+                parent.add_child(child_id.to_string());
+            }
+        }
+    }
+
+    /// 계층 관계가 제거된 뒤 parent_id/children_ids 반영
+    pub(crate) fn unlink_hierarchy(&mut self, relation: &Relation) {
+        let Some((parent_id, child_id)) = relation.parent_and_child() else {
+            return;
+        };
+
+        // Parent와 Child 관계가 같은 쌍을 함께 가리킬 수 있으므로 남은 관계 확인
+        let still_linked = self.parent_relations(child_id)
+            .any(|r| r.parent_and_child() == Some((parent_id, child_id)));
+        if still_linked {
+            return;
+        }
+
+        if let Some(mut child) = self.get_node_mut(child_id) {
+            if child.parent_id.as_deref() == Some(parent_id) {
+                child.parent_id = None;
+                child.updated_at = chrono::Utc::now();
+            }
+        }
+        if let Some(mut parent) = self.get_node_mut(parent_id) {
+            parent.remove_child(child_id);
+        }
+    }
+
+    /// 노드를 자식으로 하는 계층 관계들 (들어오는 Hierarchy/Parent, 나가는 Child)
+    pub fn parent_relations<'a>(&'a self, child_id: &str) -> impl Iterator<Item = &'a Relation> {
+        self.incoming_relations_of_type(child_id, &RelationType::Hierarchy)
+            .chain(self.incoming_relations_of_type(child_id, &RelationType::Parent))
+            .chain(self.outgoing_relations_of_type(child_id, &RelationType::Child))
+    }
+
+    /// 관계 없이 parent_id만 남아있는 연결 정리
+    pub(crate) fn detach_from_parent(&mut self, child_id: &str) {
+        let Some(parent_id) = self.get_node(child_id).and_then(|n| n.parent_id.clone()) else {
+            return;
+        };
+        if let Some(mut child) = self.get_node_mut(child_id) {
+            child.parent_id = None;
+        }
+        if let Some(mut parent) = self.get_node_mut(&parent_id) {
+            parent.remove_child(child_id);
+        }
+    }
+
+    /// 이전 버전 파일의 계층 정보를 복구하고 고친 항목 수 반환
+    ///
+    /// parent_id가 기준이 된다. 관계만 있고 parent_id가 없으면 관계에서 채우고,
+    /// 부모가 여럿이면 parent_id와 다른 관계를 지우며, children_ids는 다시 만든다.
+    pub fn repair_hierarchy(&mut self) -> usize {
+        self.record_all(None);
+        let mut fixes = 0;
+
+        // 1. 존재하지 않는 부모를 가리키는 parent_id 제거
+        let dangling: Vec<String> = self.nodes.values()
+            .filter(|n| n.parent_id.as_ref().is_some_and(|p| !self.nodes.contains_key(p)))
+            .map(|n| n.id.clone())
+            .collect();
+        for id in dangling {
+            if let Some(node) = self.nodes.get_mut(&id) {
+                node.parent_id = None;
+                fixes += 1;
+            }
+        }
+
+        // 1-1. parent_id 순환 끊기
+        let ids: Vec<String> = self.nodes.keys().cloned().collect();
+        for id in ids {
+            let mut visited = HashSet::new();
+            let mut current = id;
+            while let Some(parent_id) = self.nodes.get(&current).and_then(|n| n.parent_id.clone()) {
+                if !visited.insert(current.clone()) || parent_id == current || visited.contains(&parent_id) {
+                    if let Some(node) = self.nodes.get_mut(&current) {
+                        node.parent_id = None;
+                        fixes += 1;
+                    }
+                    break;
+                }
+                current = parent_id;
+            }
+        }
+
+        // 2. 관계만 있고 parent_id가 비어있으면 관계에서 채우기
+        let from_relations: Vec<(String, String)> = self.relations.iter()
+            .filter_map(|r| r.parent_and_child())
+            .map(|(p, c)| (p.to_string(), c.to_string()))
+            .collect();
+        for (parent_id, child_id) in &from_relations {
+            if !self.nodes.contains_key(parent_id) {
+                continue;
+            }
+            if self.is_descendant_of(parent_id, child_id) {
+                continue; // 순환이 생기는 관계는 아래에서 제거
+            }
+            if let Some(child) = self.nodes.get_mut(child_id) {
+                if child.parent_id.is_none() {
+                    child.parent_id = Some(parent_id.clone());
+                    fixes += 1;
+                }
+            }
+        }
+
+        // 3. parent_id와 맞지 않는 계층 관계 제거 (끊어진 관계, 두 번째 부모)
+        let before = self.relations.len();
+        let nodes = &self.nodes;
+        self.relations.retain(|r| match r.parent_and_child() {
+            Some((parent_id, child_id)) => nodes.get(child_id)
+                .is_some_and(|c| c.parent_id.as_deref() == Some(parent_id)),
+            None => true,
+        });
+        fixes += before - self.relations.len();
+        self.rebuild_index();
+
+        // 4. parent_id만 있고 관계가 없으면 Hierarchy 관계 추가
+        let missing: Vec<(String, String)> = self.nodes.values()
+            .filter_map(|n| n.parent_id.as_ref().map(|p| (p.clone(), n.id.clone())))
+            .filter(|(p, c)| !self.parent_relations(c).any(|r| r.parent_and_child() == Some((p.as_str(), c.as_str()))))
+            .collect();
+        for (parent_id, child_id) in missing {
+            self.insert_relation(Relation::new(parent_id, child_id, RelationType::Hierarchy));
+            fixes += 1;
+        }
+
+        // 5. parent_id에서 children_ids 다시 만들기
+        let mut expected: HashMap<String, Vec<String>> = HashMap::new();
+        for node in self.nodes.values() {
+            if let Some(parent_id) = &node.parent_id {
+                expected.entry(parent_id.clone()).or_default().push(node.id.clone());
+            }
+        }
+        for node in self.nodes.values_mut() {
+            let mut children = expected.remove(&node.id).unwrap_or_default();
+            // 기존 순서를 최대한 유지
+            children.sort_by_key(|id| {
+                let position = node.children_ids.iter().position(|c| c == id).unwrap_or(usize::MAX);
+                (position, id.clone())
+            });
+            if children != node.children_ids {
+                node.children_ids = children;
+                fixes += 1;
+            }
+        }
+
+        if fixes > 0 {
+            self.events.emit(GraphEvent::Reset);
+        }
+        fixes
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+use super::{Cake, Graph, GraphResult, Node, Operation, Relation};
+use super::journal::GraphRules;
+
+// 기본으로 기억하는 실행 취소 단계 수
+const DEFAULT_LIMIT: usize = 100;
+
+/// 편집하는 동안 건드린 것들의 편집 전 상태 (처음 건드릴 때 한 번만, None이면 편집 전에는 없었음)
+#[derive(Clone, Default)]
+pub(super) struct ChangeRecorder {
+    nodes: BTreeMap<String, Option<Node>>,
+    relations: BTreeMap<String, Option<Relation>>,
+    cakes: BTreeMap<String, Option<Cake>>,
+    rules: Option<Box<GraphRules>>,
+}
+
+impl ChangeRecorder {
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.relations.is_empty() && self.cakes.is_empty() && self.rules.is_none()
+    }
+
+    /// 그래프를 기록해 둔 편집 전 상태로 되돌리는 저널 항목들
+    fn inverse(self, graph: &Graph) -> Vec<Operation> {
+        let mut ops = Vec::new();
+        // 관계를 먼저 빼고 마지막에 다시 넣어야 끝점 노드가 없는 순간이 생기지 않는다
+        for id in self.relations.keys() {
+            if let Some(relation) = graph.get_relation(id) {
+                ops.push(Operation::RemoveRelation {
+                    id: id.clone(),
+                    source_id: relation.source_id.clone(),
+                    target_id: relation.target_id.clone(),
+                });
+            }
+        }
+        for (id, before) in self.nodes {
+            let exists = graph.get_node(&id).is_some();
+            match before {
+                Some(node) if exists => ops.push(Operation::UpdateNode(node)),
+                Some(node) => ops.push(Operation::AddNode(node)),
+                None if exists => ops.push(Operation::RemoveNode(id)),
+                None => {}
+            }
+        }
+        for (id, before) in self.cakes {
+            match before {
+                Some(cake) => ops.push(Operation::PutCake(cake)),
+                None if graph.get_cake(&id).is_some() => ops.push(Operation::RemoveCake(id)),
+                None => {}
+            }
+        }
+        if let Some(rules) = self.rules {
+            ops.push(Operation::SetRules(rules));
+        }
+        ops.extend(self.relations.into_values().flatten().map(Operation::AddRelation));
+        ops
+    }
+}
+
+impl Graph {
+    /// 노드를 바꾸기 직전에 호출 (기록 중이 아니면 아무것도 안 함)
+    pub(super) fn record_node(&mut self, id: &str) {
+        let before = self.nodes.get(id);
+        for recorder in &mut self.recorders {
+            recorder.nodes.entry(id.to_string()).or_insert_with(|| before.cloned());
+        }
+    }
+
+    pub(super) fn record_relation(&mut self, id: &str) {
+        let before = self.index.position(id).map(|position| &self.relations[position]);
+        for recorder in &mut self.recorders {
+            recorder.relations.entry(id.to_string()).or_insert_with(|| before.cloned());
+        }
+    }
+
+    pub(super) fn record_cake(&mut self, id: &str) {
+        let before = self.cakes.iter().find(|c| c.id == id);
+        for recorder in &mut self.recorders {
+            recorder.cakes.entry(id.to_string()).or_insert_with(|| before.cloned());
+        }
+    }
+
+    pub(super) fn record_rules(&mut self) {
+        if self.recorders.iter().any(|recorder| recorder.rules.is_none()) {
+            let rules = self.rules();
+            for recorder in &mut self.recorders {
+                recorder.rules.get_or_insert_with(|| Box::new(rules.clone()));
+            }
+        }
+    }
+
+    /// 그래프 전체를 직접 고치기 직전에 호출 (other가 있으면 그 그래프로 바뀌는 경우)
+    pub(super) fn record_all(&mut self, other: Option<&Graph>) {
+        if self.recorders.is_empty() {
+            return;
+        }
+        let nodes: Vec<String> = self.nodes.keys().chain(other.into_iter().flat_map(|g| g.nodes.keys())).cloned().collect();
+        let relations: Vec<String> = self.relations.iter().chain(other.into_iter().flat_map(|g| &g.relations)).map(|r| r.id.clone()).collect();
+        let cakes: Vec<String> = self.cakes.iter().chain(other.into_iter().flat_map(|g| &g.cakes)).map(|c| c.id.clone()).collect();
+        nodes.iter().for_each(|id| self.record_node(id));
+        relations.iter().for_each(|id| self.record_relation(id));
+        cakes.iter().for_each(|id| self.record_cake(id));
+        self.record_rules();
+    }
+
+    // 편집을 기록하면서 실행하고 결과와 기록 반환
+    fn recording<T>(&mut self, edit: impl FnOnce(&mut Graph) -> T) -> (T, ChangeRecorder) {
+        self.recorders.push(ChangeRecorder::default());
+        let result = edit(self);
+        (result, self.recorders.pop().unwrap_or_default())
+    }
+
+    /// 편집을 실행하고, 실패하면 편집이 그때까지 바꾼 것만 되돌리기 (반쯤 바뀐 상태가 남지 않음)
+    pub(super) fn atomically<T>(&mut self, edit: impl FnOnce(&mut Graph) -> GraphResult<T>) -> GraphResult<T> {
+        let (result, recorder) = self.recording(edit);
+        if result.is_err() {
+            self.roll_back(recorder);
+        }
+        result
+    }
+
+    fn roll_back(&mut self, recorder: ChangeRecorder) {
+        for op in recorder.inverse(self) {
+            self.apply_operation(&op);
+        }
+    }
+
+    // 저널 항목들을 적용하고, 적용한 것을 되돌리는 항목들 반환
+    fn apply_recorded(&mut self, ops: &[Operation]) -> Vec<Operation> {
+        let ((), recorder) = self.recording(|graph| ops.iter().for_each(|op| graph.apply_operation(op)));
+        recorder.inverse(self)
+    }
+}
+
+/// 실행 취소할 수 있는 편집 한 단계 (편집을 되돌리는 저널 항목들)
+#[derive(Clone)]
+struct EditStep {
+    label: String,
+    undo: Vec<Operation>,
+}
+
+/// 그래프 편집 기록 (실행 취소/다시 실행)
+///
+/// 편집은 `apply`로 실행한다. 드래그처럼 여러 프레임에 걸친 편집은 `begin_group`/`end_group`으로 묶어 한 단계로 만든다.
+/// 그래프 전체를 복사하지 않고, 편집이 건드린 노드/관계/케이크의 이전 상태만 기억한다.
+#[derive(Clone)]
+pub struct EditHistory {
+    undo_stack: Vec<EditStep>,
+    redo_stack: Vec<EditStep>,
+    group: Option<String>,
+    limit: usize,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EditHistory {
+    pub fn new() -> Self {
+        Self::with_limit(DEFAULT_LIMIT)
+    }
+
+    /// 기억할 단계 수를 정해서 만들기 (오래된 단계부터 버림)
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            group: None,
+            limit: limit.max(1),
+        }
+    }
+
+    /// 편집을 실행하고 한 단계로 기록 (실패하면 편집이 바꾼 것만 되돌리고 기록하지 않음)
+    pub fn apply<T>(&mut self, graph: &mut Graph, label: &str, edit: impl FnOnce(&mut Graph) -> GraphResult<T>) -> GraphResult<T> {
+        let (result, recorder) = graph.recording(edit);
+        match result {
+            Ok(value) => {
+                // 묶는 중이면 묶음의 기록에 이미 들어가 있다
+                if self.group.is_none() {
+                    self.push(label, recorder.inverse(graph));
+                }
+                Ok(value)
+            }
+            Err(e) => {
+                graph.roll_back(recorder);
+                Err(e)
+            }
+        }
+    }
+
+    /// 여러 편집을 한 단계로 묶기 시작 (이미 묶는 중이면 무시)
+    pub fn begin_group(&mut self, graph: &mut Graph, label: &str) {
+        if self.group.is_none() {
+            graph.recorders.push(ChangeRecorder::default());
+            self.group = Some(label.to_string());
+        }
+    }
+
+    /// 묶은 편집을 한 단계로 기록 (아무것도 바뀌지 않았으면 기록하지 않음)
+    pub fn end_group(&mut self, graph: &mut Graph) {
+        if let Some(label) = self.group.take() {
+            let recorder = graph.recorders.pop().unwrap_or_default();
+            if !recorder.is_empty() {
+                let undo = recorder.inverse(graph);
+                self.push(&label, undo);
+            }
+        }
+    }
+
+    pub fn in_group(&self) -> bool {
+        self.group.is_some()
+    }
+
+    fn push(&mut self, label: &str, undo: Vec<Operation>) {
+        self.push_undo(EditStep {
+            label: label.to_string(),
+            undo,
+        });
+        self.redo_stack.clear();
+    }
+
+    fn push_undo(&mut self, step: EditStep) {
+        self.undo_stack.push(step);
+        if self.undo_stack.len() > self.limit {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// 마지막 편집 취소 (취소한 단계 이름 반환)
+    pub fn undo(&mut self, graph: &mut Graph) -> Option<String> {
+        self.end_group(graph);
+        let step = self.undo_stack.pop()?;
+        let redo = graph.apply_recorded(&step.undo);
+        self.redo_stack.push(EditStep {
+            label: step.label.clone(),
+            undo: redo,
+        });
+        Some(step.label)
+    }
+
+    /// 취소한 편집 다시 실행 (다시 실행한 단계 이름 반환)
+    pub fn redo(&mut self, graph: &mut Graph) -> Option<String> {
+        self.end_group(graph);
+        let step = self.redo_stack.pop()?;
+        let undo = graph.apply_recorded(&step.undo);
+        self.push_undo(EditStep {
+            label: step.label.clone(),
+            undo,
+        });
+        Some(step.label)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// 다음에 취소될 단계 이름
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo_stack.last().map(|s| s.label.as_str())
+    }
+
+    /// 다음에 다시 실행될 단계 이름
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo_stack.last().map(|s| s.label.as_str())
+    }
+
+    /// 기록 모두 지우기 (다른 워크스페이스를 열었을 때 등)
+    pub fn clear(&mut self, graph: &mut Graph) {
+        if self.group.take().is_some() {
+            graph.recorders.pop();
+        }
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use super::{Relation, RelationType};
+
+type EdgeMap = HashMap<String, NodeEdges>;
+
+/// 한 노드의 한쪽 방향 관계 ID들 (모두 추가된 순서)
+#[derive(Clone, Default)]
+struct NodeEdges {
+    all: Vec<String>,
+    by_type: HashMap<RelationType, Vec<String>>,
+}
+
+/// 관계 조회용 인접 인덱스 (파일에는 저장하지 않고 불러올 때 다시 만든다)
+#[derive(Clone, Default)]
+pub(super) struct AdjacencyIndex {
+    /// 관계 ID → relations 벡터 안의 위치
+    positions: HashMap<String, usize>,
+    /// 노드 ID → 관계 타입 → 나가는 관계 ID들
+    outgoing: EdgeMap,
+    /// 노드 ID → 관계 타입 → 들어오는 관계 ID들
+    incoming: EdgeMap,
+}
+
+impl AdjacencyIndex {
+    pub(super) fn build(relations: &[Relation]) -> Self {
+        let mut index = Self::default();
+        for (position, relation) in relations.iter().enumerate() {
+            index.insert(relation, position);
+        }
+        index
+    }
+
+    pub(super) fn insert(&mut self, relation: &Relation, position: usize) {
+        self.positions.insert(relation.id.clone(), position);
+        Self::add_edge(&mut self.outgoing, &relation.source_id, relation);
+        Self::add_edge(&mut self.incoming, &relation.target_id, relation);
+    }
+
+    /// 관계를 swap_remove로 지운 뒤 호출 (마지막 관계가 지운 자리로 옮겨온다)
+    pub(super) fn remove(&mut self, relation: &Relation, removed_position: usize, relations: &[Relation]) {
+        self.positions.remove(&relation.id);
+        Self::remove_edge(&mut self.outgoing, &relation.source_id, relation);
+        Self::remove_edge(&mut self.incoming, &relation.target_id, relation);
+
+        if let Some(moved) = relations.get(removed_position) {
+            self.positions.insert(moved.id.clone(), removed_position);
+        }
+    }
+
+    pub(super) fn position(&self, relation_id: &str) -> Option<usize> {
+        self.positions.get(relation_id).copied()
+    }
+
+    pub(super) fn outgoing(&self, node_id: &str) -> impl Iterator<Item = &String> {
+        Self::all_edges(&self.outgoing, node_id)
+    }
+
+    pub(super) fn incoming(&self, node_id: &str) -> impl Iterator<Item = &String> {
+        Self::all_edges(&self.incoming, node_id)
+    }
+
+    pub(super) fn outgoing_of_type(&self, node_id: &str, relation_type: &RelationType) -> &[String] {
+        Self::typed_edges(&self.outgoing, node_id, relation_type)
+    }
+
+    pub(super) fn incoming_of_type(&self, node_id: &str, relation_type: &RelationType) -> &[String] {
+        Self::typed_edges(&self.incoming, node_id, relation_type)
+    }
+
+    fn add_edge(edges: &mut EdgeMap, node_id: &str, relation: &Relation) {
+        let node_edges = edges.entry(node_id.to_string()).or_default();
+        node_edges.all.push(relation.id.clone());
+        node_edges.by_type
+            .entry(relation.relation_type.clone())
+            .or_default()
+            .push(relation.id.clone());
+    }
+
+    fn remove_edge(edges: &mut EdgeMap, node_id: &str, relation: &Relation) {
+        let Some(node_edges) = edges.get_mut(node_id) else {
+            return;
+        };
+        node_edges.all.retain(|id| *id != relation.id);
+        if let Some(ids) = node_edges.by_type.get_mut(&relation.relation_type) {
+            ids.retain(|id| *id != relation.id);
+            if ids.is_empty() {
+                node_edges.by_type.remove(&relation.relation_type);
+            }
+        }
+        if node_edges.all.is_empty() {
+            edges.remove(node_id);
+        }
+    }
+
+    fn all_edges<'a>(edges: &'a EdgeMap, node_id: &str) -> impl Iterator<Item = &'a String> {
+        edges.get(node_id)
+            .into_iter()
+            .flat_map(|node_edges| &node_edges.all)
+    }
+
+    fn typed_edges<'a>(edges: &'a EdgeMap, node_id: &str, relation_type: &RelationType) -> &'a [String] {
+        edges.get(node_id)
+            .and_then(|node_edges| node_edges.by_type.get(relation_type))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use super::{Cake, EnforcementMode, Graph, GraphEvent, LayerDef, Node, NodeTypeRegistry, Relation, RelationTypeRegistry, SubscriptionId};
+
+/// 타입 정의, 층, 규칙 적용 방식
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GraphRules {
+    pub node_types: NodeTypeRegistry,
+    pub relation_types: RelationTypeRegistry,
+    pub layers: Vec<LayerDef>,
+    pub hierarchy_mode: EnforcementMode,
+    pub dependency_mode: EnforcementMode,
+}
+
+/// 저널에 남는 변경 하나
+///
+/// 바뀐 뒤의 상태를 그대로 담으므로 같은 항목을 여러 번 적용해도 결과가 같다.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "op", content = "data")]
+pub enum Operation {
+    AddNode(Node),
+    UpdateNode(Node),
+    RemoveNode(String),
+    AddRelation(Relation),
+    UpdateRelation(Relation),
+    RemoveRelation {
+        id: String,
+        source_id: String,
+        target_id: String,
+    },
+    PutCake(Cake),
+    RemoveCake(String),
+    SetRules(Box<GraphRules>),
+    /// 그래프 전체 (실행 취소처럼 한꺼번에 바뀐 경우)
+    Snapshot(Box<Graph>),
+}
+
+impl Operation {
+    /// 이 변경이 노드와 관련 있는지 (노드 자체 또는 노드에 연결된 관계)
+    pub fn touches_node(&self, node_id: &str) -> bool {
+        match self {
+            Operation::AddNode(node) | Operation::UpdateNode(node) => node.id == node_id,
+            Operation::RemoveNode(id) => id == node_id,
+            Operation::AddRelation(relation) | Operation::UpdateRelation(relation) => {
+                relation.source_id == node_id || relation.target_id == node_id
+            }
+            Operation::RemoveRelation { source_id, target_id, .. } => source_id == node_id || target_id == node_id,
+            Operation::PutCake(_) | Operation::RemoveCake(_) | Operation::SetRules(_) => false,
+            Operation::Snapshot(graph) => graph.get_node(node_id).is_some(),
+        }
+    }
+
+    pub fn is_snapshot(&self) -> bool {
+        matches!(self, Operation::Snapshot(_))
+    }
+
+    /// 바로 앞의 변경 earlier를 대신할 수 있는지 (같은 대상의 상태를 다시 기록한 경우)
+    pub fn replaces(&self, earlier: &Operation) -> bool {
+        match (earlier, self) {
+            (Operation::UpdateNode(a), Operation::UpdateNode(b)) => a.id == b.id,
+            (Operation::UpdateRelation(a), Operation::UpdateRelation(b)) => a.id == b.id,
+            (Operation::PutCake(a), Operation::PutCake(b)) => a.id == b.id,
+            (Operation::SetRules(_), Operation::SetRules(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// 화면에 보여줄 한 줄 설명
+    pub fn summary(&self) -> String {
+        match self {
+            Operation::AddNode(node) => format!("Added node '{}'", node.title),
+            Operation::UpdateNode(node) => format!("Updated node '{}'", node.title),
+            Operation::RemoveNode(id) => format!("Removed node {}", id),
+            Operation::AddRelation(relation) => format!("Added {} relation", relation.relation_type.name()),
+            Operation::UpdateRelation(relation) => format!("Updated {} relation", relation.relation_type.name()),
+            Operation::RemoveRelation { id, .. } => format!("Removed relation {}", id),
+            Operation::PutCake(cake) => format!("Saved cake '{}'", cake.title),
+            Operation::RemoveCake(id) => format!("Removed cake {}", id),
+            Operation::SetRules(_) => "Changed types and rules".to_string(),
+            Operation::Snapshot(_) => "Replaced whole graph".to_string(),
+        }
+    }
+}
+
+/// 언제 어떤 변경이 있었는지
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub at: DateTime<Utc>,
+    pub op: Operation,
+}
+
+/// 그래프 변경을 저널 항목으로 모으는 기록기
+///
+/// 그래프 이벤트를 구독하고, `collect`할 때 바뀐 노드/관계/케이크의 현재 상태를 읽어 항목을 만든다.
+/// 항목의 시간은 이벤트가 일어난 시각이다.
+pub struct Journal {
+    subscription: SubscriptionId,
+}
+
+impl Journal {
+    /// 이후의 변경을 기록하기 시작
+    pub fn attach(graph: &mut Graph) -> Self {
+        Self {
+            subscription: graph.subscribe(),
+        }
+    }
+
+    pub fn detach(self, graph: &mut Graph) {
+        graph.unsubscribe(self.subscription);
+    }
+
+    /// 마지막으로 모은 뒤에 생긴 변경들 (일어난 순서)
+    pub fn collect(&mut self, graph: &mut Graph) -> Vec<JournalEntry> {
+        let events = graph.take_timed_events(self.subscription);
+        let Some(last) = events.last() else {
+            return Vec::new();
+        };
+
+        // 전체가 바뀌었으면 현재 그래프 하나로 충분하다
+        if events.iter().any(|timed| timed.event == GraphEvent::Reset) {
+            return vec![JournalEntry {
+                at: last.at,
+                op: Operation::Snapshot(Box::new(graph.clone())),
+            }];
+        }
+
+        // 같은 이벤트가 뒤에 또 있으면 뒤의 것만 남긴다 (어차피 현재 상태를 읽음)
+        events.iter()
+            .enumerate()
+            .filter(|(i, timed)| !events[i + 1..].iter().any(|later| later.event == timed.event))
+            .filter_map(|(_, timed)| Some(JournalEntry {
+                at: timed.at,
+                op: Self::operation(graph, &timed.event)?,
+            }))
+            .collect()
+    }
+
+    /// 아직 쓰지 않은 항목들 뒤에 새 항목 덧붙이기 (바로 앞 항목과 같은 대상을 다시 기록한 것이면 바꿔 넣음)
+    pub fn append(pending: &mut Vec<JournalEntry>, entries: impl IntoIterator<Item = JournalEntry>) {
+        for entry in entries {
+            match pending.last_mut() {
+                Some(last) if entry.op.replaces(&last.op) => *last = entry,
+                _ => pending.push(entry),
+            }
+        }
+    }
+
+    fn operation(graph: &Graph, event: &GraphEvent) -> Option<Operation> {
+        let op = match event {
+            GraphEvent::NodeAdded(id) => Operation::AddNode(graph.get_node(id)?.clone()),
+            GraphEvent::NodeUpdated(id) => Operation::UpdateNode(graph.get_node(id)?.clone()),
+            GraphEvent::NodeRemoved(id) => Operation::RemoveNode(id.clone()),
+            GraphEvent::RelationAdded(id) => Operation::AddRelation(graph.get_relation(id)?.clone()),
+            GraphEvent::RelationUpdated(id) => Operation::UpdateRelation(graph.get_relation(id)?.clone()),
+            GraphEvent::RelationRemoved { id, source_id, target_id } => Operation::RemoveRelation {
+                id: id.clone(),
+                source_id: source_id.clone(),
+                target_id: target_id.clone(),
+            },
+            GraphEvent::CakeUpdated(id) => Operation::PutCake(graph.get_cake(id)?.clone()),
+            GraphEvent::CakeRemoved(id) => Operation::RemoveCake(id.clone()),
+            GraphEvent::RulesChanged => Operation::SetRules(Box::new(graph.rules())),
+            GraphEvent::Reset => Operation::Snapshot(Box::new(graph.clone())),
+        };
+        Some(op)
+    }
+}
+
+impl Graph {
+    /// 저널 항목 하나 적용 (검사 없이 기록된 상태 그대로)
+    pub fn apply_operation(&mut self, op: &Operation) {
+        match op {
+            Operation::AddNode(node) | Operation::UpdateNode(node) => {
+                self.put_node(node.clone());
+            }
+            Operation::RemoveNode(id) => {
+                self.take_node(id);
+            }
+            Operation::AddRelation(relation) | Operation::UpdateRelation(relation) => self.put_relation(relation.clone()),
+            Operation::RemoveRelation { id, .. } => {
+                self.take_relation(id);
+            }
+            Operation::PutCake(cake) => {
+                self.add_cake(cake.clone());
+            }
+            Operation::RemoveCake(id) => {
+                self.take_cake(id);
+            }
+            Operation::SetRules(rules) => self.set_rules((**rules).clone()),
+            Operation::Snapshot(graph) => {
+                self.replace_with((**graph).clone());
+            }
+        }
+    }
+
+    /// 스냅샷 이후의 저널 항목들을 순서대로 적용해서 상태 복원
+    pub fn replay<'a>(&mut self, entries: impl IntoIterator<Item = &'a JournalEntry>) {
+        for entry in entries {
+            self.apply_operation(&entry.op);
+        }
+    }
+}
@@ -1,11 +1,43 @@
 mod graph;
+mod guard;
+mod cake;
+mod error;
+mod validation;
+mod hierarchy;
+mod index;
+mod traversal;
+mod dependency;
+mod diff;
+mod evolution;
+mod subtree;
+mod tags;
+mod history;
+mod events;
+mod journal;
 mod node;
 mod relation;
 mod node_type;
+mod type_registry;
+mod schema;
 mod position;
+mod property;
 
 pub use graph::Graph;
+pub use guard::{CakeMut, NodeMut};
+pub use cake::{Cake, CakeScope};
+pub use error::{GraphError, GraphResult};
+pub use validation::{EnforcementMode, HierarchyViolation};
+pub use subtree::DeletePolicy;
+pub use history::EditHistory;
+pub use events::{GraphEvent, SubscriptionId, TimedEvent};
+pub use journal::{GraphRules, Journal, JournalEntry, Operation};
+pub use tags::{normalize_tag, tag_matches};
+pub use diff::{FieldChange, NodeDiff, NodeField};
+pub use traversal::{Bfs, Dfs, Direction, GraphPath, TraversalFilter};
 pub use node::Node;
 pub use relation::{Relation, RelationType};
 pub use node_type::NodeType;
-pub use position::{Position2D, Position3D, LayerPosition};
\ No newline at end of file
+pub use type_registry::{NodeTypeDef, NodeTypeRegistry, RelationTypeDef, RelationTypeRegistry};
+pub use schema::{LayerDef, UniverseSchema};
+pub use position::{Position2D, Position3D, LayerPosition};
+pub use property::{Properties, PropertyValue};
\ No newline at end of file
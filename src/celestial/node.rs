@@ -1,7 +1,9 @@
+use std::collections::BTreeSet;
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 use super::{Position2D, Position3D, LayerPosition};
-use super::NodeType;
+use super::{NodeType, NodeTypeRegistry};
+use super::Properties;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Node {
@@ -19,6 +21,8 @@ pub struct Node {
     pub children_ids: Vec<String>,  // 자식 노드들의 ID
     
     // 계층 구조 정보
+    #[serde(default)]
+    pub cake_id: Option<String>, // 어느 케이크에 속하는지
     pub layer: usize,           // 케이크의 몇 번째 층인지
     pub layer_radius: f32,      // 해당 층에서의 반지름
     pub layer_angle: f32,       // 해당 층에서의 각도
@@ -32,6 +36,14 @@ pub struct Node {
     pub custom_color: Option<egui::Color32>,
     pub custom_color_rgba: Option<[u8; 4]>,
     pub custom_size: Option<f32>,
+
+    // 사용자 정의 속성 (담당자, 예상 시간, URL 등)
+    #[serde(default)]
+    pub properties: Properties,
+
+    // 계층형 태그 ("area/backend"). Graph::add_tag나 get_node_mut으로 바꿔야 태그 인덱스가 맞는다
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
     
     // 3D 전용 속성
     pub is_visible: bool,
@@ -60,9 +72,9 @@ impl Node {
             position,
             position_3d: Position3D::new(position.x, position.y, layer as f32 * 100.0),
             layer_position: None,
-            node_type,
             parent_id: None,
             children_ids: Vec::new(),
+            cake_id: None,
             layer,
             layer_radius: 100.0 + layer as f32 * 50.0, // 층마다 반지름 증가
             layer_angle: 0.0,
@@ -71,12 +83,15 @@ impl Node {
             custom_color: None,
             custom_color_rgba: None,
             custom_size: None,
+            properties: Properties::new(),
+            tags: BTreeSet::new(),
             is_visible: true,
             opacity: 1.0,
             rotation: [0.0, 0.0, 0.0],
             scale: 1.0,
             color: Self::get_default_color(&node_type),
             size: Self::get_default_size(&node_type),
+            node_type,
             selected: false,
         }
     }
@@ -97,9 +112,9 @@ impl Node {
             position: Position2D::new(x, z), // 2D 호환용
             position_3d: Position3D::new(x, y, z),
             layer_position: None,
-            node_type,
             parent_id: None,
             children_ids: Vec::new(),
+            cake_id: None,
             layer,
             layer_radius: radius,
             layer_angle: angle,
@@ -108,12 +123,15 @@ impl Node {
             custom_color: None,
             custom_color_rgba: None,
             custom_size: None,
+            properties: Properties::new(),
+            tags: BTreeSet::new(),
             is_visible: true,
             opacity: 1.0,
             rotation: [0.0, 0.0, 0.0],
             scale: 1.0,
             color: Self::get_default_color(&node_type),
             size: Self::get_default_size(&node_type),
+            node_type,
             selected: false,
         }
     }
@@ -123,6 +141,11 @@ impl Node {
         self
     }
 
+    pub fn with_cake(mut self, cake_id: String) -> Self {
+        self.cake_id = Some(cake_id);
+        self
+    }
+
     pub fn with_description(mut self, description: String) -> Self {
         self.description = Some(description);
         self
@@ -169,38 +192,15 @@ impl Node {
         self.updated_at = chrono::Utc::now();
     }
 
-    /// 노드 타입에 따른 기본 색상
+    /// 노드 타입에 따른 기본 색상 (기본 프리셋 기준)
     fn get_default_color(node_type: &NodeType) -> [f32; 4] {
-        match node_type {
-            NodeType::Root => [1.0, 0.8, 0.0, 1.0],      // 골드
-            NodeType::Concept => [0.0, 0.6, 1.0, 1.0],   // 블루
-            NodeType::Task => [1.0, 0.4, 0.6, 1.0],      // 핑크
-            NodeType::Note => [0.4, 1.0, 0.4, 1.0],      // 그린
-            NodeType::Evolution => [0.8, 0.4, 1.0, 1.0], // 퍼플
-            NodeType::Star => [1.0, 0.9, 0.0, 1.0],      // 노란색
-            NodeType::Planet => [0.0, 0.7, 1.0, 1.0],    // 파란색
-            NodeType::Satellite => [0.7, 0.7, 0.7, 1.0], // 회색
-            NodeType::Asteroid => [0.5, 0.3, 0.1, 1.0],  // 갈색
-            NodeType::Category => [0.8, 0.8, 0.8, 1.0],  // 라이트 그레이
-            NodeType::Base => [0.6, 0.6, 0.6, 1.0],      // 그레이
-        }
+        let [r, g, b, a] = NodeTypeRegistry::builtin_ref().color(node_type);
+        [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0]
     }
 
-    /// 노드 타입에 따른 기본 크기
+    /// 노드 타입에 따른 기본 크기 (기본 프리셋 기준)
     fn get_default_size(node_type: &NodeType) -> f32 {
-        match node_type {
-            NodeType::Root => 50.0,
-            NodeType::Concept => 40.0,
-            NodeType::Task => 30.0,
-            NodeType::Note => 25.0,
-            NodeType::Evolution => 35.0,
-            NodeType::Star => 45.0,
-            NodeType::Planet => 35.0,
-            NodeType::Satellite => 25.0,
-            NodeType::Asteroid => 20.0,
-            NodeType::Category => 40.0,
-            NodeType::Base => 30.0,
-        }
+        NodeTypeRegistry::builtin_ref().default_size(node_type)
     }
 
     /// 케이크 레이어에서의 크기 (거리에 따른 스케일링)
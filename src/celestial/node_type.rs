@@ -1,6 +1,10 @@
 use serde::{Serialize, Deserialize};
+use super::NodeTypeRegistry;
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+/// 노드 타입
+///
+/// `Custom`이 이름(String)을 들고 있어서 `Copy`가 아니다. 값을 넘길 때는 참조나 `clone()`을 쓴다.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum NodeType {
     // 3D 케이크 구조용 타입들
     Root,       // 최하위층 (골드)
@@ -18,13 +22,32 @@ pub enum NodeType {
     // 추가 타입들
     Category,   // 카테고리
     Base,       // 기본
+
+    // 유니버스에서 직접 정의한 타입 (NodeTypeRegistry에 등록된 이름)
+    Custom(String),
 }
 
 impl NodeType {
-    pub fn display_name(&self) -> &'static str {
+    /// 기본 프리셋에 들어있는 타입들
+    pub const BUILTIN: [NodeType; 11] = [
+        NodeType::Root,
+        NodeType::Concept,
+        NodeType::Task,
+        NodeType::Note,
+        NodeType::Evolution,
+        NodeType::Star,
+        NodeType::Planet,
+        NodeType::Satellite,
+        NodeType::Asteroid,
+        NodeType::Category,
+        NodeType::Base,
+    ];
+
+    /// 레지스트리에서 쓰는 타입 이름
+    pub fn name(&self) -> &str {
         match self {
             NodeType::Root => "Root",
-            NodeType::Concept => "Concept", 
+            NodeType::Concept => "Concept",
             NodeType::Task => "Task",
             NodeType::Note => "Note",
             NodeType::Evolution => "Evolution",
@@ -34,80 +57,43 @@ impl NodeType {
             NodeType::Asteroid => "Asteroid",
             NodeType::Category => "Category",
             NodeType::Base => "Base",
+            NodeType::Custom(name) => name,
         }
     }
 
+    /// 이름으로 타입 찾기 (기본 타입이 아니면 Custom)
+    pub fn from_name(name: &str) -> NodeType {
+        Self::BUILTIN.iter()
+            .find(|t| t.name() == name)
+            .cloned()
+            .unwrap_or_else(|| NodeType::Custom(name.to_string()))
+    }
+
+    pub fn display_name(&self) -> &str {
+        self.name()
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, NodeType::Custom(_))
+    }
+
+    /// 기본 프리셋 기준 자식 타입들 (그래프별 규칙은 Graph::node_types)
     pub fn get_valid_children(&self) -> Vec<NodeType> {
-        match self {
-            // 3D 케이크 구조 계층
-            NodeType::Root => vec![NodeType::Concept, NodeType::Category],
-            NodeType::Concept => vec![NodeType::Task, NodeType::Note],
-            NodeType::Task => vec![NodeType::Note],
-            NodeType::Note => vec![],
-            NodeType::Evolution => vec![NodeType::Evolution], // 진화 체인
-            
-            // 기존 우주 테마 계층
-            NodeType::Star => vec![NodeType::Planet],
-            NodeType::Planet => vec![NodeType::Satellite],
-            NodeType::Satellite => vec![NodeType::Asteroid],
-            NodeType::Asteroid => vec![],
-            
-            // 추가 타입들
-            NodeType::Category => vec![NodeType::Base, NodeType::Concept],
-            NodeType::Base => vec![NodeType::Task, NodeType::Note],
-        }
+        NodeTypeRegistry::builtin_ref().valid_children(self)
     }
 
-    /// 다음 계층의 기본 타입 반환
+    /// 다음 계층의 기본 타입 반환 (첫 번째 자식 타입)
     pub fn next_level(&self) -> Option<NodeType> {
-        match self {
-            NodeType::Root => Some(NodeType::Concept),
-            NodeType::Concept => Some(NodeType::Task),
-            NodeType::Task => Some(NodeType::Note),
-            NodeType::Note => None,
-            
-            NodeType::Star => Some(NodeType::Planet),
-            NodeType::Planet => Some(NodeType::Satellite),
-            NodeType::Satellite => Some(NodeType::Asteroid),
-            NodeType::Asteroid => None,
-            
-            NodeType::Category => Some(NodeType::Base),
-            NodeType::Base => Some(NodeType::Task),
-            NodeType::Evolution => Some(NodeType::Evolution),
-        }
+        self.get_valid_children().into_iter().next()
     }
 
-    /// 케이크 계층에서의 레벨 (0부터 시작)
+    /// 케이크 계층에서의 레벨 (0부터 시작, 기본 프리셋 기준)
     pub fn cake_layer(&self) -> usize {
-        match self {
-            NodeType::Root => 0,
-            NodeType::Concept | NodeType::Category => 1,
-            NodeType::Task | NodeType::Base => 2,
-            NodeType::Note => 3,
-            NodeType::Evolution => 4,
-            
-            // 우주 테마는 기본 레벨로 매핑
-            NodeType::Star => 0,
-            NodeType::Planet => 1,
-            NodeType::Satellite => 2,
-            NodeType::Asteroid => 3,
-        }
+        NodeTypeRegistry::builtin_ref().default_layer(self)
     }
 
-    /// 타입에 따른 이모지 반환
+    /// 타입에 따른 이모지 반환 (기본 프리셋 기준)
     pub fn emoji(&self) -> &'static str {
-        match self {
-            NodeType::Root => "🌌",
-            NodeType::Concept => "💭",
-            NodeType::Task => "📋",
-            NodeType::Note => "📝",
-            NodeType::Evolution => "🔄",
-            NodeType::Star => "⭐",
-            NodeType::Planet => "🪐",
-            NodeType::Satellite => "🛰️",
-            NodeType::Asteroid => "☄️",
-            NodeType::Category => "📁",
-            NodeType::Base => "🔧",
-        }
+        NodeTypeRegistry::builtin_ref().emoji(self)
     }
-} 
\ No newline at end of file
+}
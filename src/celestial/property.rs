@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use serde::{Serialize, Deserialize};
+use super::{Graph, GraphError, GraphResult};
+
+/// 노드/관계에 붙이는 속성들 (키 순서로 저장)
+pub type Properties = BTreeMap<String, PropertyValue>;
+
+/// 타입이 있는 속성 값
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum PropertyValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Date(chrono::NaiveDate),
+    List(Vec<PropertyValue>),
+    /// 다른 노드의 ID
+    NodeRef(String),
+}
+
+impl PropertyValue {
+    /// 값 타입의 표시 이름
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            PropertyValue::String(_) => "String",
+            PropertyValue::Number(_) => "Number",
+            PropertyValue::Bool(_) => "Bool",
+            PropertyValue::Date(_) => "Date",
+            PropertyValue::List(_) => "List",
+            PropertyValue::NodeRef(_) => "Node",
+        }
+    }
+
+    /// 참조하는 노드 ID들 (리스트 안쪽까지)
+    pub fn node_refs(&self) -> Vec<&str> {
+        match self {
+            PropertyValue::NodeRef(id) => vec![id.as_str()],
+            PropertyValue::List(items) => items.iter().flat_map(|v| v.node_refs()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// 복사된 노드를 가리키도록 참조 ID 바꾸기 (맵에 없는 ID는 그대로)
+    pub(crate) fn remap_node_refs(&mut self, id_map: &HashMap<String, String>) {
+        match self {
+            PropertyValue::NodeRef(id) => {
+                if let Some(new_id) = id_map.get(id) {
+                    *id = new_id.clone();
+                }
+            }
+            PropertyValue::List(items) => items.iter_mut().for_each(|v| v.remap_node_refs(id_map)),
+            _ => {}
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            PropertyValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_date(&self) -> Option<chrono::NaiveDate> {
+        match self {
+            PropertyValue::Date(d) => Some(*d),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::String(s) => write!(f, "{}", s),
+            PropertyValue::Number(n) => write!(f, "{}", n),
+            PropertyValue::Bool(b) => write!(f, "{}", b),
+            PropertyValue::Date(d) => write!(f, "{}", d.format("%Y-%m-%d")),
+            PropertyValue::List(items) => {
+                let items: Vec<String> = items.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            PropertyValue::NodeRef(id) => write!(f, "@{}", id),
+        }
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        PropertyValue::String(value.to_string())
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        PropertyValue::String(value)
+    }
+}
+
+impl From<f64> for PropertyValue {
+    fn from(value: f64) -> Self {
+        PropertyValue::Number(value)
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        PropertyValue::Bool(value)
+    }
+}
+
+impl From<chrono::NaiveDate> for PropertyValue {
+    fn from(value: chrono::NaiveDate) -> Self {
+        PropertyValue::Date(value)
+    }
+}
+
+impl Graph {
+    /// 노드 속성 가져오기
+    pub fn node_property(&self, node_id: &str, key: &str) -> Option<&PropertyValue> {
+        self.get_node(node_id)?.properties.get(key)
+    }
+
+    /// 노드 속성 설정 (이전 값 반환, 참조하는 노드가 없으면 거부)
+    pub fn set_node_property(&mut self, node_id: &str, key: impl Into<String>, value: PropertyValue) -> GraphResult<Option<PropertyValue>> {
+        self.check_node_refs(&value)?;
+        let mut node = self.get_node_mut(node_id)
+            .ok_or_else(|| GraphError::UnknownNode(node_id.to_string()))?;
+        node.updated_at = chrono::Utc::now();
+        Ok(node.properties.insert(key.into(), value))
+    }
+
+    /// 노드 속성 제거 (제거된 값 반환)
+    pub fn remove_node_property(&mut self, node_id: &str, key: &str) -> GraphResult<Option<PropertyValue>> {
+        if self.node_property(node_id, key).is_none() {
+            self.require_node(node_id)?;
+            return Ok(None);
+        }
+        let mut node = self.get_node_mut(node_id)
+            .ok_or_else(|| GraphError::UnknownNode(node_id.to_string()))?;
+        node.updated_at = chrono::Utc::now();
+        Ok(node.properties.remove(key))
+    }
+
+    /// 관계 속성 가져오기
+    pub fn relation_property(&self, relation_id: &str, key: &str) -> Option<&PropertyValue> {
+        self.get_relation(relation_id)?.properties.get(key)
+    }
+
+    /// 관계 속성 설정 (이전 값 반환, 참조하는 노드가 없으면 거부)
+    pub fn set_relation_property(&mut self, relation_id: &str, key: impl Into<String>, value: PropertyValue) -> GraphResult<Option<PropertyValue>> {
+        self.check_node_refs(&value)?;
+        let mut relation = self.get_relation_mut(relation_id)?;
+        Ok(relation.properties.insert(key.into(), value))
+    }
+
+    /// 관계 속성 제거 (제거된 값 반환)
+    pub fn remove_relation_property(&mut self, relation_id: &str, key: &str) -> GraphResult<Option<PropertyValue>> {
+        if self.relation_property(relation_id, key).is_none() {
+            self.get_relation(relation_id)
+                .ok_or_else(|| GraphError::UnknownRelation(relation_id.to_string()))?;
+            return Ok(None);
+        }
+        Ok(self.get_relation_mut(relation_id)?.properties.remove(key))
+    }
+
+    fn check_node_refs(&self, value: &PropertyValue) -> GraphResult<()> {
+        for id in value.node_refs() {
+            self.require_node(id)?;
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod relation;
 
 pub use relation::{Relation, RelationType}; 
\ No newline at end of file
@@ -1,7 +1,7 @@
 use serde::{Serialize, Deserialize};
-use crate::celestial::NodeType;
+use crate::celestial::{NodeType, NodeTypeRegistry, Properties, RelationTypeRegistry};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RelationType {
     // 3D 케이크 구조용 관계들
     Parent,     // 부모-자식 관계 (계층간 연결)
@@ -17,11 +17,27 @@ pub enum RelationType {
     // 추가 관계 타입들
     Dependency, // 의존성 관계
     Association, // 연관 관계
+
+    // 유니버스에서 직접 정의한 관계 (RelationTypeRegistry에 등록된 이름)
+    Custom(String),
 }
 
 impl RelationType {
-    /// 관계 타입의 표시 이름
-    pub fn display_name(&self) -> &'static str {
+    /// 기본 프리셋에 들어있는 관계 타입들
+    pub const BUILTIN: [RelationType; 9] = [
+        RelationType::Parent,
+        RelationType::Child,
+        RelationType::Sibling,
+        RelationType::Orbit,
+        RelationType::Evolution,
+        RelationType::Reference,
+        RelationType::Hierarchy,
+        RelationType::Dependency,
+        RelationType::Association,
+    ];
+
+    /// 레지스트리에서 쓰는 관계 타입 이름
+    pub fn name(&self) -> &str {
         match self {
             RelationType::Parent => "Parent",
             RelationType::Child => "Child",
@@ -32,33 +48,38 @@ impl RelationType {
             RelationType::Hierarchy => "Hierarchy",
             RelationType::Dependency => "Dependency",
             RelationType::Association => "Association",
+            RelationType::Custom(name) => name,
         }
     }
 
-    /// 관계의 색상 (시각화용)
+    /// 이름으로 관계 타입 찾기 (기본 타입이 아니면 Custom)
+    pub fn from_name(name: &str) -> RelationType {
+        Self::BUILTIN.iter()
+            .find(|t| t.name() == name)
+            .cloned()
+            .unwrap_or_else(|| RelationType::Custom(name.to_string()))
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, RelationType::Custom(_))
+    }
+
+    /// 관계 타입의 표시 이름 (기본 프리셋 기준)
+    pub fn display_name(&self) -> &str {
+        RelationTypeRegistry::builtin_ref().get(self)
+            .map(|def| def.display_name.as_str())
+            .unwrap_or(self.name())
+    }
+
+    /// 관계의 색상 (시각화용, 기본 프리셋 기준)
     pub fn color(&self) -> [f32; 4] {
-        match self {
-            RelationType::Parent => [1.0, 0.8, 0.0, 1.0],    // 골드
-            RelationType::Child => [0.8, 1.0, 0.0, 1.0],     // 라이트 그린
-            RelationType::Sibling => [0.0, 0.8, 1.0, 1.0],   // 블루
-            RelationType::Orbit => [0.6, 0.8, 1.0, 1.0],     // 라이트 블루
-            RelationType::Evolution => [0.6, 1.0, 0.6, 1.0], // 그린
-            RelationType::Reference => [1.0, 0.6, 0.6, 1.0], // 핑크
-            RelationType::Hierarchy => [0.8, 0.8, 0.8, 1.0], // 그레이
-            RelationType::Dependency => [1.0, 0.6, 0.0, 1.0], // 오렌지
-            RelationType::Association => [0.8, 0.6, 1.0, 1.0], // 퍼플
-        }
+        let [r, g, b, a] = RelationTypeRegistry::builtin_ref().color(self);
+        [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0]
     }
 
-    /// 관계의 두께 (시각화용)
+    /// 관계의 두께 (시각화용, 기본 프리셋 기준)
     pub fn thickness(&self) -> f32 {
-        match self {
-            RelationType::Parent | RelationType::Child => 3.0, // 계층 관계는 굵게
-            RelationType::Hierarchy => 2.5,
-            RelationType::Evolution => 2.0,
-            RelationType::Dependency => 2.0,
-            _ => 1.5,
-        }
+        RelationTypeRegistry::builtin_ref().thickness(self)
     }
 }
 
@@ -70,6 +91,8 @@ pub struct Relation {
     pub relation_type: RelationType,
     pub label: Option<String>,
     pub weight: f32,
+    #[serde(default)]
+    pub properties: Properties,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -82,6 +105,7 @@ impl Relation {
             relation_type,
             label: None,
             weight: 1.0,
+            properties: Properties::new(),
             created_at: chrono::Utc::now(),
         }
     }
@@ -96,25 +120,23 @@ impl Relation {
         self
     }
 
-    /// 계층 관계가 유효한지 확인
+    /// 계층 관계가 유효한지 확인 (기본 프리셋 기준, 그래프별 규칙은 Graph::node_types)
     pub fn is_valid_hierarchy(source_type: &NodeType, target_type: &NodeType) -> bool {
-        matches!(
-            (source_type, target_type),
-            // 3D 케이크 구조 계층
-            (NodeType::Root, NodeType::Concept) |
-            (NodeType::Root, NodeType::Category) |
-            (NodeType::Concept, NodeType::Task) |
-            (NodeType::Concept, NodeType::Note) |
-            (NodeType::Task, NodeType::Note) |
-            (NodeType::Category, NodeType::Base) |
-            (NodeType::Base, NodeType::Task) |
-            (NodeType::Base, NodeType::Note) |
-            
-            // 기존 우주 테마 계층 (하위 호환성)
-            (NodeType::Star, NodeType::Planet) | 
-            (NodeType::Planet, NodeType::Satellite) |
-            (NodeType::Satellite, NodeType::Asteroid)
-        )
+        NodeTypeRegistry::builtin_ref().allows_child(source_type, target_type)
+    }
+
+    /// 계층을 나타내는 관계인지 확인 (Hierarchy, Parent, Child)
+    pub fn is_hierarchical(&self) -> bool {
+        matches!(self.relation_type, RelationType::Hierarchy | RelationType::Parent | RelationType::Child)
+    }
+
+    /// 계층 관계의 (부모 ID, 자식 ID) 반환
+    pub fn parent_and_child(&self) -> Option<(&str, &str)> {
+        match self.relation_type {
+            RelationType::Hierarchy | RelationType::Parent => Some((&self.source_id, &self.target_id)),
+            RelationType::Child => Some((&self.target_id, &self.source_id)),
+            _ => None,
+        }
     }
 
     /// 부모-자식 관계인지 확인
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use super::{EnforcementMode, Graph, GraphError, GraphEvent, GraphResult, NodeType, NodeTypeDef, NodeTypeRegistry, RelationTypeDef, RelationTypeRegistry};
+
+/// 케이크 층 하나의 정의
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LayerDef {
+    pub name: String,
+    /// 이 층에 둘 수 있는 노드 타입 이름들 (비어있으면 모두)
+    #[serde(default)]
+    pub node_types: Vec<String>,
+}
+
+impl LayerDef {
+    pub fn new<'a>(name: impl Into<String>, node_types: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            name: name.into(),
+            node_types: node_types.into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    pub fn allows(&self, node_type: &NodeType) -> bool {
+        self.node_types.is_empty() || self.node_types.iter().any(|t| t == node_type.name())
+    }
+}
+
+/// 케이크 방법론을 정의하는 유니버스 스키마 (JSON 파일)
+///
+/// 층 순서, 층마다 둘 수 있는 노드 타입, 부모/자식 타입 쌍(`allowed_children`),
+/// 관계 타입 제한을 담는다. 노드 타입의 기본 층은 처음 나오는 층으로 정해진다.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UniverseSchema {
+    pub name: String,
+    #[serde(default)]
+    pub layers: Vec<LayerDef>,
+    #[serde(default)]
+    pub node_types: Vec<NodeTypeDef>,
+    #[serde(default)]
+    pub relation_types: Vec<RelationTypeDef>,
+}
+
+impl UniverseSchema {
+    /// 기존 NodeType/RelationType 값들로 만든 기본 케이크 스키마
+    pub fn builtin() -> Self {
+        let node_types: Vec<NodeTypeDef> = NodeTypeRegistry::builtin().types().cloned().collect();
+        let layer_count = node_types.iter().map(|t| t.default_layer + 1).max().unwrap_or(0);
+        let layers = (0..layer_count)
+            .map(|layer| LayerDef::new(
+                format!("Layer {}", layer + 1),
+                node_types.iter().filter(|t| t.default_layer == layer).map(|t| t.name.as_str()),
+            ))
+            .collect();
+
+        Self {
+            name: "Cake".to_string(),
+            layers,
+            node_types,
+            relation_types: RelationTypeRegistry::builtin().types().cloned().collect(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> GraphResult<Self> {
+        serde_json::from_str(json).map_err(|e| GraphError::InvalidSchema(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> GraphResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| GraphError::InvalidSchema(e.to_string()))
+    }
+
+    /// 스키마 파일 읽기
+    pub fn load(path: impl AsRef<Path>) -> GraphResult<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| GraphError::InvalidSchema(format!("{}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+
+    /// 스키마 파일로 저장
+    pub fn save(&self, path: impl AsRef<Path>) -> GraphResult<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()?)
+            .map_err(|e| GraphError::InvalidSchema(format!("{}: {}", path.display(), e)))
+    }
+
+    /// 스키마로 타입 레지스트리 만들기 (층에 맞춰 기본 층을 정하고 이름 참조를 검사)
+    fn registries(&self) -> GraphResult<(NodeTypeRegistry, RelationTypeRegistry)> {
+        let mut node_types = NodeTypeRegistry::empty();
+        for def in &self.node_types {
+            check_name(&def.name)?;
+            let mut def = def.clone();
+            if let Some(layer) = self.layers.iter().position(|l| l.node_types.contains(&def.name)) {
+                def.default_layer = layer;
+            }
+            node_types.register(def);
+        }
+
+        let known = |name: &String| -> GraphResult<()> {
+            if node_types.get_by_name(name).is_some() {
+                Ok(())
+            } else {
+                Err(GraphError::InvalidSchema(format!("unknown node type '{}'", name)))
+            }
+        };
+        for layer in &self.layers {
+            layer.node_types.iter().try_for_each(known)?;
+        }
+        for def in node_types.types() {
+            def.allowed_children.iter().try_for_each(known)?;
+        }
+
+        let mut relation_types = RelationTypeRegistry::empty();
+        let mut seen = HashSet::new();
+        for def in &self.relation_types {
+            check_name(&def.name)?;
+            if !seen.insert(def.name.as_str()) {
+                return Err(GraphError::InvalidSchema(format!("relation type '{}' is defined twice", def.name)));
+            }
+            def.allowed_sources.iter().chain(&def.allowed_targets).try_for_each(known)?;
+            relation_types.register(def.clone());
+        }
+
+        Ok((node_types, relation_types))
+    }
+}
+
+fn check_name(name: &str) -> GraphResult<()> {
+    if name.is_empty() || name.trim() != name {
+        return Err(GraphError::InvalidSchema(format!("invalid type name '{}'", name)));
+    }
+    Ok(())
+}
+
+impl Graph {
+    /// 스키마를 따르는 빈 그래프
+    pub fn with_schema(schema: &UniverseSchema) -> GraphResult<Self> {
+        let mut graph = Self::new();
+        graph.apply_schema(schema)?;
+        Ok(graph)
+    }
+
+    /// 스키마의 층과 타입 정의로 교체 (스키마가 잘못되었으면 그래프는 그대로)
+    ///
+    /// 스키마에 없는 타입은 기본 타입이라도 등록되지 않은 타입이 된다.
+    pub fn apply_schema(&mut self, schema: &UniverseSchema) -> GraphResult<()> {
+        let (node_types, relation_types) = schema.registries()?;
+        self.record_rules();
+        self.node_types = node_types;
+        self.relation_types = relation_types;
+        self.layers = schema.layers.clone();
+        self.events.emit(GraphEvent::RulesChanged);
+        Ok(())
+    }
+
+    /// 현재 층과 타입 정의를 스키마로 내보내기
+    pub fn schema(&self, name: impl Into<String>) -> UniverseSchema {
+        UniverseSchema {
+            name: name.into(),
+            layers: self.layers.clone(),
+            node_types: self.node_types.types().cloned().collect(),
+            relation_types: self.relation_types.types().cloned().collect(),
+        }
+    }
+
+    /// 스키마에서 정한 층들 (비어있으면 층 제한 없음)
+    pub fn layers(&self) -> &[LayerDef] {
+        &self.layers
+    }
+
+    /// 층 이름 (정의되지 않은 층이면 None)
+    pub fn layer_name(&self, layer: usize) -> Option<&str> {
+        self.layers.get(layer).map(|l| l.name.as_str())
+    }
+
+    /// 노드 타입을 이 층에 둘 수 있는지
+    pub fn allows_on_layer(&self, node_type: &NodeType, layer: usize) -> bool {
+        if self.layers.is_empty() {
+            return true;
+        }
+        self.layers.get(layer).is_some_and(|l| l.allows(node_type))
+    }
+
+    /// 층 규칙 검사 (계층 규칙과 같은 모드로 거부/경고)
+    pub(crate) fn check_layer(&mut self, node_type: &NodeType, layer: usize) -> GraphResult<()> {
+        if self.hierarchy_mode() == EnforcementMode::Off || self.allows_on_layer(node_type, layer) {
+            return Ok(());
+        }
+        let error = GraphError::LayerNotAllowed {
+            node_type: node_type.clone(),
+            layer,
+        };
+        self.enforce(self.hierarchy_mode(), error)
+    }
+}
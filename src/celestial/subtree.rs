@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use uuid::Uuid;
+use super::{Cake, Graph, GraphResult, Node, Relation, RelationType};
+
+/// 자식이 있는 노드를 지울 때 자식들을 처리하는 방법
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeletePolicy {
+    /// 자손까지 모두 삭제
+    Cascade,
+    /// 자식들을 지운 노드의 부모 밑으로 올림 (부모가 없으면 최상위 노드)
+    PromoteToGrandparent,
+    /// 자식들을 최상위 노드로 분리
+    #[default]
+    Orphan,
+}
+
+impl DeletePolicy {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DeletePolicy::Cascade => "Delete subtree",
+            DeletePolicy::PromoteToGrandparent => "Promote children",
+            DeletePolicy::Orphan => "Orphan children",
+        }
+    }
+}
+
+impl Graph {
+    /// 노드와 모든 자손의 ID (전위 순서, 자기 자신이 처음)
+    pub fn subtree_ids(&self, root_id: &str) -> Vec<String> {
+        let mut ids = Vec::new();
+        let mut stack = vec![root_id.to_string()];
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.get_node(&id) else {
+                continue;
+            };
+            if ids.contains(&id) {
+                continue; // 손상된 파일의 순환 방지
+            }
+            stack.extend(node.children_ids.iter().rev().cloned());
+            ids.push(id);
+        }
+        ids
+    }
+
+    /// 자손과 함께 다른 부모 밑으로 옮기기 (None이면 최상위로)
+    ///
+    /// 계층 규칙과 순환을 다시 검사하고, 옮긴 노드들의 층과 케이크를 새 부모에 맞춘다.
+    pub fn move_subtree(&mut self, node_id: &str, new_parent_id: Option<&str>) -> GraphResult<()> {
+        let (layer, cake_id) = self.subtree_target(node_id, new_parent_id)?;
+        self.check_subtree_layers(node_id, layer)?;
+        self.set_parent(node_id, new_parent_id)?;
+        self.relayer_subtree(node_id, layer, cake_id);
+        Ok(())
+    }
+
+    // 서브트리를 새 부모 밑으로 옮겼을 때 루트의 층과 케이크
+    fn subtree_target(&self, node_id: &str, new_parent_id: Option<&str>) -> GraphResult<(usize, Option<String>)> {
+        let node = self.require_node(node_id)?;
+        Ok(match new_parent_id.and_then(|id| self.get_node(id)) {
+            // 부모가 케이크에 속하지 않으면 원래 케이크 유지
+            Some(parent) => (parent.layer + 1, parent.cake_id.clone().or_else(|| node.cake_id.clone())),
+            None => (self.node_types().default_layer(&node.node_type), node.cake_id.clone()),
+        })
+    }
+
+    // 서브트리의 노드들을 루트 층에서부터의 깊이에 맞는 층에 둘 수 있는지 검사
+    fn check_subtree_layers(&mut self, root_id: &str, root_layer: usize) -> GraphResult<()> {
+        let mut depths: HashMap<String, usize> = HashMap::from([(root_id.to_string(), root_layer)]);
+        for id in self.subtree_ids(root_id) {
+            let layer = depths[&id];
+            let node = &self.nodes[&id];
+            for child_id in &node.children_ids {
+                depths.entry(child_id.clone()).or_insert(layer + 1);
+            }
+            let node_type = node.node_type.clone();
+            self.check_layer(&node_type, layer)?;
+        }
+        Ok(())
+    }
+
+    /// 서브트리를 새 ID로 복사하고 복사본 루트의 ID 반환
+    ///
+    /// 서브트리 안쪽끼리의 관계와 노드 참조 속성도 새 ID에 맞춰 바꾼다. 바깥 노드와의 관계는 복사하지 않는다.
+    pub fn duplicate_subtree(&mut self, root_id: &str, new_parent_id: Option<&str>) -> GraphResult<String> {
+        self.require_node(root_id)?;
+        if let Some(parent_id) = new_parent_id {
+            self.require_node(parent_id)?;
+            // 노드를 넣기 전에 새 부모 밑에 둘 수 있는지 확인
+            self.check_hierarchy(&Relation::new(parent_id.to_string(), root_id.to_string(), RelationType::Hierarchy))?;
+            let (layer, _) = self.subtree_target(root_id, new_parent_id)?;
+            self.check_subtree_layers(root_id, layer)?;
+        } else {
+            // 제자리에 복사하면 원래 층을 그대로 쓴다
+            for id in self.subtree_ids(root_id) {
+                let node = &self.nodes[&id];
+                let (node_type, layer) = (node.node_type.clone(), node.layer);
+                self.check_layer(&node_type, layer)?;
+            }
+        }
+
+        let old_ids = self.subtree_ids(root_id);
+        let id_map: HashMap<String, String> = old_ids.iter()
+            .map(|id| (id.clone(), Uuid::new_v4().to_string()))
+            .collect();
+
+        let now = chrono::Utc::now();
+        for old_id in &old_ids {
+            let mut copy = self.nodes[old_id].clone();
+            copy.id = id_map[old_id].clone();
+            copy.parent_id = copy.parent_id.as_ref().and_then(|p| id_map.get(p)).cloned();
+            copy.children_ids = copy.children_ids.iter()
+                .filter_map(|c| id_map.get(c))
+                .cloned()
+                .collect();
+            copy.created_at = now;
+            copy.updated_at = now;
+            copy.selected = false;
+            copy.properties.values_mut().for_each(|v| v.remap_node_refs(&id_map));
+            self.put_node(copy);
+        }
+
+        let internal: Vec<Relation> = self.relations.iter()
+            .filter(|r| id_map.contains_key(&r.source_id) && id_map.contains_key(&r.target_id))
+            .cloned()
+            .collect();
+        for relation in internal {
+            let mut copy = relation;
+            copy.id = Uuid::new_v4().to_string();
+            copy.source_id = id_map[&copy.source_id].clone();
+            copy.target_id = id_map[&copy.target_id].clone();
+            copy.properties.values_mut().for_each(|v| v.remap_node_refs(&id_map));
+            copy.created_at = now;
+            self.insert_relation(copy);
+        }
+
+        let new_root_id = id_map[root_id].clone();
+        if new_parent_id.is_some() {
+            if let Err(e) = self.move_subtree(&new_root_id, new_parent_id) {
+                // 옮기지 못하면 복사본도 남기지 않음
+                for copy_id in id_map.values() {
+                    let _ = self.remove_node(copy_id);
+                }
+                return Err(e);
+            }
+        }
+        Ok(new_root_id)
+    }
+
+    /// 정책에 따라 노드 삭제 후 지워진 노드들 반환 (처음이 요청한 노드)
+    pub fn remove_node_with_policy(&mut self, node_id: &str, policy: DeletePolicy) -> GraphResult<Vec<Node>> {
+        self.require_node(node_id)?;
+
+        match policy {
+            DeletePolicy::Cascade => {
+                let ids = self.subtree_ids(node_id);
+                // 자식부터 지워야 부모를 지울 때 고아 처리가 일어나지 않음
+                let mut removed = Vec::new();
+                for id in ids.iter().rev() {
+                    removed.push(self.remove_node(id)?);
+                }
+                removed.reverse();
+                Ok(removed)
+            }
+            DeletePolicy::PromoteToGrandparent => {
+                let grandparent_id = self.get_node(node_id).and_then(|n| n.parent_id.clone());
+                let children: Vec<String> = self.get_node(node_id)
+                    .map(|n| n.children_ids.clone())
+                    .unwrap_or_default();
+
+                // 하나라도 조부모 밑에 둘 수 없으면(계층 규칙이나 층 규칙) 아무것도 바꾸지 않음
+                for child_id in &children {
+                    if let Some(grandparent_id) = &grandparent_id {
+                        self.check_hierarchy(&Relation::new(grandparent_id.clone(), child_id.clone(), RelationType::Hierarchy))?;
+                    }
+                    let (layer, _) = self.subtree_target(child_id, grandparent_id.as_deref())?;
+                    self.check_subtree_layers(child_id, layer)?;
+                }
+
+                // 미리 알 수 없는 실패(관계 타입 제한 등)로 중간에 멈추면 옮긴 자식들도 되돌림
+                self.atomically(|graph| {
+                    for child_id in &children {
+                        graph.move_subtree(child_id, grandparent_id.as_deref())?;
+                    }
+                    Ok(vec![graph.remove_node(node_id)?])
+                })
+            }
+            DeletePolicy::Orphan => Ok(vec![self.remove_node(node_id)?]),
+        }
+    }
+
+    // 서브트리의 층을 부모로부터의 깊이에 맞게 다시 계산 (케이크에 속하면 새 층의 반지름으로, 각도는 그대로)
+    fn relayer_subtree(&mut self, root_id: &str, root_layer: usize, cake_id: Option<String>) {
+        let mut depths: HashMap<String, usize> = HashMap::from([(root_id.to_string(), root_layer)]);
+        let now = chrono::Utc::now();
+        for id in self.subtree_ids(root_id) {
+            let layer = depths[&id];
+            let Some(node) = self.nodes.get(&id) else {
+                continue;
+            };
+            for child_id in &node.children_ids {
+                depths.entry(child_id.clone()).or_insert(layer + 1);
+            }
+            if node.layer == layer && node.cake_id == cake_id {
+                continue;
+            }
+            if let Some(mut node) = self.get_node_mut(&id) {
+                node.layer = layer;
+                node.position_3d.y = layer as f32 * 100.0;
+                node.cake_id = cake_id.clone();
+                if node.cake_id.is_some() {
+                    let angle = node.layer_angle;
+                    node.update_cake_position(Cake::node_radius(layer), angle);
+                }
+                node.updated_at = now;
+            }
+        }
+
+        let deepest = depths.values().copied().max().unwrap_or(root_layer);
+        if let Some(cake_id) = cake_id {
+            let _ = self.expand_cake(&cake_id, deepest);
+        }
+    }
+}
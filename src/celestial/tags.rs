@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, BTreeSet};
+use super::{Graph, GraphError, GraphResult, Node};
+
+/// 태그 정리 ("/area//backend/ " → "area/backend", 비어있으면 None)
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let segments: Vec<&str> = tag.split('/')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("/"))
+    }
+}
+
+/// tag가 prefix와 같거나 그 하위 태그인지 ("area/backend"는 "area"에 포함)
+pub fn tag_matches(tag: &str, prefix: &str) -> bool {
+    tag == prefix || tag.strip_prefix(prefix).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// 태그 → 노드 ID 인덱스 (파일에는 저장하지 않고 불러올 때 다시 만든다)
+///
+/// 이름순으로 두어 하위 태그("area/...")들이 한 구간에 모이게 한다.
+#[derive(Clone, Default)]
+pub(super) struct TagIndex {
+    nodes_by_tag: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl TagIndex {
+    pub(super) fn build<'a>(nodes: impl Iterator<Item = &'a Node>) -> Self {
+        let mut index = Self::default();
+        for node in nodes {
+            index.insert(node);
+        }
+        index
+    }
+
+    pub(super) fn insert(&mut self, node: &Node) {
+        for tag in &node.tags {
+            self.nodes_by_tag.entry(tag.clone()).or_default().insert(node.id.clone());
+        }
+    }
+
+    pub(super) fn remove(&mut self, node: &Node) {
+        for tag in &node.tags {
+            self.remove_entry(tag, &node.id);
+        }
+    }
+
+    // 태그와 그 하위 태그들의 노드 ID ("area/"로 시작하는 이름은 "area/"와 "area0" 사이에 있다)
+    fn matching(&self, prefix: &str) -> impl Iterator<Item = (&String, &BTreeSet<String>)> {
+        let exact = self.nodes_by_tag.get_key_value(prefix);
+        let below = self.nodes_by_tag.range(format!("{}/", prefix)..format!("{}0", prefix));
+        exact.into_iter().chain(below)
+    }
+
+    fn remove_entry(&mut self, tag: &str, node_id: &str) {
+        if let Some(ids) = self.nodes_by_tag.get_mut(tag) {
+            ids.remove(node_id);
+            if ids.is_empty() {
+                self.nodes_by_tag.remove(tag);
+            }
+        }
+    }
+}
+
+impl Graph {
+    /// 노드에 태그 추가 (새로 붙었으면 true)
+    pub fn add_tag(&mut self, node_id: &str, tag: &str) -> GraphResult<bool> {
+        self.require_node(node_id)?;
+        let Some(tag) = normalize_tag(tag) else {
+            return Ok(false);
+        };
+        if self.require_node(node_id)?.tags.contains(&tag) {
+            return Ok(false);
+        }
+        // 태그 인덱스는 핸들을 놓을 때 맞춰진다
+        let mut node = self.get_node_mut(node_id)
+            .ok_or_else(|| GraphError::UnknownNode(node_id.to_string()))?;
+        node.tags.insert(tag);
+        node.updated_at = chrono::Utc::now();
+        Ok(true)
+    }
+
+    /// 노드에서 태그 제거 (있었으면 true)
+    pub fn remove_tag(&mut self, node_id: &str, tag: &str) -> GraphResult<bool> {
+        self.require_node(node_id)?;
+        let Some(tag) = normalize_tag(tag) else {
+            return Ok(false);
+        };
+        if !self.require_node(node_id)?.tags.contains(&tag) {
+            return Ok(false);
+        }
+        let mut node = self.get_node_mut(node_id)
+            .ok_or_else(|| GraphError::UnknownNode(node_id.to_string()))?;
+        node.tags.remove(&tag);
+        node.updated_at = chrono::Utc::now();
+        Ok(true)
+    }
+
+    /// 노드의 태그를 통째로 바꾸기
+    pub fn set_tags<'a>(&mut self, node_id: &str, tags: impl IntoIterator<Item = &'a str>) -> GraphResult<()> {
+        let new_tags: BTreeSet<String> = tags.into_iter().filter_map(normalize_tag).collect();
+        let old_tags = self.require_node(node_id)?.tags.clone();
+        for tag in old_tags.difference(&new_tags) {
+            self.remove_tag(node_id, tag)?;
+        }
+        for tag in new_tags.difference(&old_tags) {
+            self.add_tag(node_id, tag)?;
+        }
+        Ok(())
+    }
+
+    /// 태그가 붙은 노드들 (하위 태그 포함, 먼저 만든 순)
+    pub fn nodes_with_tag(&self, tag: &str) -> Vec<&Node> {
+        let Some(tag) = normalize_tag(tag) else {
+            return Vec::new();
+        };
+        let ids: BTreeSet<&String> = self.tag_index.matching(&tag)
+            .flat_map(|(_, ids)| ids)
+            .collect();
+        let mut nodes: Vec<&Node> = ids.into_iter().filter_map(|id| self.get_node(id)).collect();
+        nodes.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        nodes
+    }
+
+    /// 노드가 태그(또는 그 하위 태그)를 가지고 있는지
+    pub fn node_has_tag(&self, node_id: &str, tag: &str) -> bool {
+        let Some(tag) = normalize_tag(tag) else {
+            return false;
+        };
+        self.get_node(node_id)
+            .is_some_and(|n| n.tags.iter().any(|t| tag_matches(t, &tag)))
+    }
+
+    /// 쓰이고 있는 모든 태그와 노드 수 (이름순)
+    pub fn all_tags(&self) -> Vec<(&str, usize)> {
+        self.tag_index.nodes_by_tag.iter()
+            .map(|(tag, ids)| (tag.as_str(), ids.len()))
+            .collect()
+    }
+
+    /// 태그 이름 바꾸기 (하위 태그도 함께, 이미 있는 태그면 합쳐짐). 바뀐 노드 수 반환
+    pub fn rename_tag(&mut self, from: &str, to: &str) -> usize {
+        self.retag_nodes(from, to).len()
+    }
+
+    /// 여러 태그를 하나로 합치기. 바뀐 노드 수 반환
+    pub fn merge_tags(&mut self, sources: &[&str], into: &str) -> usize {
+        let mut changed = BTreeSet::new();
+        for source in sources {
+            changed.extend(self.retag_nodes(source, into));
+        }
+        changed.len()
+    }
+
+    fn retag_nodes(&mut self, from: &str, to: &str) -> BTreeSet<String> {
+        let mut changed = BTreeSet::new();
+        let (Some(from), Some(to)) = (normalize_tag(from), normalize_tag(to)) else {
+            return changed;
+        };
+        if from == to {
+            return changed;
+        }
+
+        let ids: BTreeSet<String> = self.tag_index.matching(&from)
+            .flat_map(|(_, ids)| ids.iter().cloned())
+            .collect();
+
+        // 새 이름이 다른 옛 이름과 겹칠 수 있으므로 노드마다 태그 집합을 한 번에 바꾼다
+        let now = chrono::Utc::now();
+        for id in &ids {
+            if let Some(mut node) = self.get_node_mut(id) {
+                node.tags = node.tags.iter()
+                    .map(|t| if tag_matches(t, &from) {
+                        format!("{}{}", to, &t[from.len()..])
+                    } else {
+                        t.clone()
+                    })
+                    .collect();
+                node.updated_at = now;
+            }
+        }
+        changed.extend(ids);
+        changed
+    }
+}
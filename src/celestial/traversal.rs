@@ -0,0 +1,279 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use super::{Graph, Node, Relation, RelationType};
+
+/// 관계를 따라갈 방향
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// source → target 방향으로만
+    Outgoing,
+    /// target → source 방향으로만
+    Incoming,
+    /// 방향 무시
+    #[default]
+    Both,
+}
+
+/// 탐색할 때 따라갈 관계 조건
+#[derive(Clone, Debug, Default)]
+pub struct TraversalFilter {
+    pub direction: Direction,
+    /// None이면 모든 관계 타입
+    pub relation_types: Option<Vec<RelationType>>,
+}
+
+impl TraversalFilter {
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            relation_types: None,
+        }
+    }
+
+    pub fn with_types(mut self, relation_types: impl IntoIterator<Item = RelationType>) -> Self {
+        self.relation_types = Some(relation_types.into_iter().collect());
+        self
+    }
+
+    pub fn allows(&self, relation_type: &RelationType) -> bool {
+        self.relation_types.as_ref()
+            .is_none_or(|types| types.contains(relation_type))
+    }
+}
+
+/// 두 노드 사이의 경로
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphPath {
+    /// 시작 노드부터 도착 노드까지
+    pub node_ids: Vec<String>,
+    /// node_ids 사이를 잇는 관계들 (node_ids보다 하나 적음)
+    pub relation_ids: Vec<String>,
+    /// 관계 개수 또는 가중치 합
+    pub cost: f32,
+}
+
+impl GraphPath {
+    /// 경로에 포함된 관계 개수
+    pub fn len(&self) -> usize {
+        self.relation_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.relation_ids.is_empty()
+    }
+}
+
+/// 너비 우선 탐색 (노드와 시작점으로부터의 거리)
+pub struct Bfs<'a> {
+    graph: &'a Graph,
+    filter: TraversalFilter,
+    queue: VecDeque<(&'a str, usize)>,
+    visited: HashSet<&'a str>,
+}
+
+impl<'a> Iterator for Bfs<'a> {
+    type Item = (&'a Node, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node_id, depth) = self.queue.pop_front()?;
+        for (_, neighbor_id) in self.graph.edges(node_id, &self.filter) {
+            if self.visited.insert(neighbor_id) {
+                self.queue.push_back((neighbor_id, depth + 1));
+            }
+        }
+        self.graph.get_node(node_id).map(|node| (node, depth))
+    }
+}
+
+/// 깊이 우선 탐색 (전위 순서, 노드와 시작점으로부터의 깊이)
+pub struct Dfs<'a> {
+    graph: &'a Graph,
+    filter: TraversalFilter,
+    stack: Vec<(&'a str, usize)>,
+    visited: HashSet<&'a str>,
+}
+
+impl<'a> Iterator for Dfs<'a> {
+    type Item = (&'a Node, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node_id, depth) = self.stack.pop()?;
+            if !self.visited.insert(node_id) {
+                continue;
+            }
+            // 먼저 추가된 관계를 먼저 방문하도록 역순으로 쌓기
+            let neighbors: Vec<&str> = self.graph.edges(node_id, &self.filter)
+                .into_iter()
+                .map(|(_, id)| id)
+                .filter(|id| !self.visited.contains(id))
+                .collect();
+            for neighbor_id in neighbors.into_iter().rev() {
+                self.stack.push((neighbor_id, depth + 1));
+            }
+            if let Some(node) = self.graph.get_node(node_id) {
+                return Some((node, depth));
+            }
+        }
+    }
+}
+
+// 다익스트라용 우선순위 큐 항목 (비용이 작은 것이 먼저)
+struct Frontier<'a> {
+    cost: f32,
+    node_id: &'a str,
+}
+
+impl PartialEq for Frontier<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Frontier<'_> {}
+
+impl PartialOrd for Frontier<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Frontier<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+            .then_with(|| other.node_id.cmp(self.node_id))
+    }
+}
+
+impl Graph {
+    /// 조건에 맞는 (관계, 이웃 노드 ID) 목록 (관계가 추가된 순서)
+    pub fn edges<'a>(&'a self, node_id: &str, filter: &TraversalFilter) -> Vec<(&'a Relation, &'a str)> {
+        let mut edges: Vec<(&Relation, &str)> = Vec::new();
+        if filter.direction != Direction::Incoming {
+            edges.extend(self.outgoing_relations(node_id)
+                .filter(|r| filter.allows(&r.relation_type))
+                .map(|r| (r, r.target_id.as_str())));
+        }
+        if filter.direction != Direction::Outgoing {
+            edges.extend(self.incoming_relations(node_id)
+                .filter(|r| filter.allows(&r.relation_type))
+                .map(|r| (r, r.source_id.as_str())));
+        }
+        edges.sort_by(|(a, _), (b, _)| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        edges
+    }
+
+    /// 바로 연결된 이웃 노드들 (중복 제외)
+    pub fn neighbors(&self, node_id: &str, filter: &TraversalFilter) -> Vec<&Node> {
+        let mut seen = HashSet::new();
+        self.edges(node_id, filter)
+            .into_iter()
+            .filter(|(_, id)| seen.insert(*id))
+            .filter_map(|(_, id)| self.get_node(id))
+            .collect()
+    }
+
+    /// 시작 노드부터 너비 우선 탐색
+    pub fn bfs(&self, start_id: &str, filter: TraversalFilter) -> Bfs<'_> {
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        if let Some(node) = self.get_node(start_id) {
+            queue.push_back((node.id.as_str(), 0));
+            visited.insert(node.id.as_str());
+        }
+        Bfs { graph: self, filter, queue, visited }
+    }
+
+    /// 시작 노드부터 깊이 우선 탐색
+    pub fn dfs(&self, start_id: &str, filter: TraversalFilter) -> Dfs<'_> {
+        let stack = self.get_node(start_id)
+            .map(|node| vec![(node.id.as_str(), 0)])
+            .unwrap_or_default();
+        Dfs { graph: self, filter, stack, visited: HashSet::new() }
+    }
+
+    /// k 단계 안에 닿는 노드들과 거리 (시작 노드 제외)
+    pub fn neighborhood(&self, start_id: &str, k: usize, filter: TraversalFilter) -> Vec<(&Node, usize)> {
+        self.bfs(start_id, filter)
+            .skip(1)
+            .take_while(|(_, depth)| *depth <= k)
+            .collect()
+    }
+
+    /// 관계 개수가 가장 적은 경로
+    pub fn shortest_path(&self, from_id: &str, to_id: &str, filter: &TraversalFilter) -> Option<GraphPath> {
+        let start = self.get_node(from_id)?.id.as_str();
+        self.get_node(to_id)?;
+
+        let mut previous: HashMap<&str, (&str, &str)> = HashMap::new();
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node_id) = queue.pop_front() {
+            if node_id == to_id {
+                return Some(self.build_path(start, node_id, &previous, |_| 1.0));
+            }
+            for (relation, neighbor_id) in self.edges(node_id, filter) {
+                if visited.insert(neighbor_id) {
+                    previous.insert(neighbor_id, (node_id, relation.id.as_str()));
+                    queue.push_back(neighbor_id);
+                }
+            }
+        }
+        None
+    }
+
+    /// Relation::weight 합이 가장 작은 경로 (음수 가중치는 0으로 취급)
+    pub fn weighted_shortest_path(&self, from_id: &str, to_id: &str, filter: &TraversalFilter) -> Option<GraphPath> {
+        let start = self.get_node(from_id)?.id.as_str();
+        self.get_node(to_id)?;
+
+        let mut costs: HashMap<&str, f32> = HashMap::from([(start, 0.0)]);
+        let mut previous: HashMap<&str, (&str, &str)> = HashMap::new();
+        let mut heap = BinaryHeap::from([Frontier { cost: 0.0, node_id: start }]);
+
+        while let Some(Frontier { cost, node_id }) = heap.pop() {
+            if node_id == to_id {
+                return Some(self.build_path(start, node_id, &previous, |r| r.weight.max(0.0)));
+            }
+            if costs.get(node_id).is_some_and(|best| cost > *best) {
+                continue;
+            }
+            for (relation, neighbor_id) in self.edges(node_id, filter) {
+                let next_cost = cost + relation.weight.max(0.0);
+                if costs.get(neighbor_id).is_none_or(|best| next_cost < *best) {
+                    costs.insert(neighbor_id, next_cost);
+                    previous.insert(neighbor_id, (node_id, relation.id.as_str()));
+                    heap.push(Frontier { cost: next_cost, node_id: neighbor_id });
+                }
+            }
+        }
+        None
+    }
+
+    fn build_path(
+        &self,
+        start: &str,
+        end: &str,
+        previous: &HashMap<&str, (&str, &str)>,
+        cost_of: impl Fn(&Relation) -> f32,
+    ) -> GraphPath {
+        let mut node_ids = vec![end.to_string()];
+        let mut relation_ids = Vec::new();
+        let mut current = end;
+        while current != start {
+            let (prev, relation_id) = previous[current];
+            relation_ids.push(relation_id.to_string());
+            node_ids.push(prev.to_string());
+            current = prev;
+        }
+        node_ids.reverse();
+        relation_ids.reverse();
+
+        let cost = relation_ids.iter()
+            .filter_map(|id| self.get_relation(id))
+            .map(cost_of)
+            .sum();
+        GraphPath { node_ids, relation_ids, cost }
+    }
+}
@@ -0,0 +1,430 @@
+use std::sync::OnceLock;
+use serde::{Serialize, Deserialize};
+use super::{Graph, GraphError, GraphEvent, GraphResult, Node, NodeType, Relation, RelationType};
+
+/// 노드 타입 하나의 정의
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeTypeDef {
+    pub name: String,
+    #[serde(default = "fallback_emoji")]
+    pub emoji: String,
+    #[serde(default = "fallback_color")]
+    pub color: [u8; 4],
+    /// 케이크에서의 기본 층 (0부터)
+    #[serde(default)]
+    pub default_layer: usize,
+    #[serde(default = "fallback_size")]
+    pub default_size: f32,
+    /// 자식으로 둘 수 있는 타입 이름들
+    #[serde(default)]
+    pub allowed_children: Vec<String>,
+}
+
+impl NodeTypeDef {
+    pub fn new(name: impl Into<String>, emoji: impl Into<String>, color: [u8; 4], default_layer: usize) -> Self {
+        Self {
+            name: name.into(),
+            emoji: emoji.into(),
+            color,
+            default_layer,
+            default_size: FALLBACK_SIZE,
+            allowed_children: Vec::new(),
+        }
+    }
+
+    pub fn with_children<'a>(mut self, children: impl IntoIterator<Item = &'a str>) -> Self {
+        self.allowed_children = children.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.default_size = size;
+        self
+    }
+
+    pub fn node_type(&self) -> NodeType {
+        NodeType::from_name(&self.name)
+    }
+}
+
+/// 유니버스에서 쓰는 노드 타입들 (유니버스와 함께 저장)
+///
+/// 스키마로 만든 레지스트리에는 스키마에 있는 타입만 있다 (기본 타입도 따로 조회하지 않음).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeTypeRegistry {
+    types: Vec<NodeTypeDef>,
+}
+
+// 등록되지 않은 타입에 쓰는 값 (스키마 파일에서 빠진 필드에도 씀)
+const FALLBACK_EMOJI: &str = "🔹";
+const FALLBACK_COLOR: [u8; 4] = [180, 180, 180, 255];
+const FALLBACK_SIZE: f32 = 30.0;
+
+fn fallback_emoji() -> String {
+    FALLBACK_EMOJI.to_string()
+}
+
+fn fallback_color() -> [u8; 4] {
+    FALLBACK_COLOR
+}
+
+fn fallback_size() -> f32 {
+    FALLBACK_SIZE
+}
+
+impl Default for NodeTypeRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl NodeTypeRegistry {
+    /// 타입이 하나도 없는 레지스트리
+    pub fn empty() -> Self {
+        Self { types: Vec::new() }
+    }
+
+    /// 기존 NodeType 값들로 만든 기본 프리셋
+    pub fn builtin() -> Self {
+        let types = vec![
+            // 3D 케이크 구조용 타입들
+            NodeTypeDef::new("Root", "🌌", [255, 204, 0, 255], 0).with_size(50.0).with_children(["Concept", "Category"]),
+            NodeTypeDef::new("Concept", "💭", [0, 153, 255, 255], 1).with_size(40.0).with_children(["Task", "Note"]),
+            NodeTypeDef::new("Task", "📋", [255, 102, 153, 255], 2).with_size(30.0).with_children(["Note"]),
+            NodeTypeDef::new("Note", "📝", [102, 255, 102, 255], 3).with_size(25.0),
+            NodeTypeDef::new("Evolution", "🔄", [204, 102, 255, 255], 4).with_size(35.0).with_children(["Evolution"]),
+            // 기존 우주 테마 타입들
+            NodeTypeDef::new("Star", "⭐", [255, 230, 0, 255], 0).with_size(45.0).with_children(["Planet"]),
+            NodeTypeDef::new("Planet", "🪐", [0, 179, 255, 255], 1).with_size(35.0).with_children(["Satellite"]),
+            NodeTypeDef::new("Satellite", "🛰️", [179, 179, 179, 255], 2).with_size(25.0).with_children(["Asteroid"]),
+            NodeTypeDef::new("Asteroid", "☄️", [128, 77, 26, 255], 3).with_size(20.0),
+            // 추가 타입들
+            NodeTypeDef::new("Category", "📁", [204, 204, 204, 255], 1).with_size(40.0).with_children(["Base", "Concept"]),
+            NodeTypeDef::new("Base", "🔧", [153, 153, 153, 255], 2).with_size(30.0).with_children(["Task", "Note"]),
+        ];
+        Self { types }
+    }
+
+    /// 프로그램 전체에서 공유하는 기본 프리셋
+    pub fn builtin_ref() -> &'static NodeTypeRegistry {
+        static BUILTIN: OnceLock<NodeTypeRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(NodeTypeRegistry::builtin)
+    }
+
+    /// 타입 정의 가져오기 (등록되지 않았으면 None)
+    pub fn get(&self, node_type: &NodeType) -> Option<&NodeTypeDef> {
+        self.get_by_name(node_type.name())
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&NodeTypeDef> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// 등록된 타입들 (등록한 순서)
+    pub fn types(&self) -> impl Iterator<Item = &NodeTypeDef> {
+        self.types.iter()
+    }
+
+    pub fn contains(&self, node_type: &NodeType) -> bool {
+        self.get(node_type).is_some()
+    }
+
+    /// 타입 등록 (같은 이름이 있으면 교체)
+    pub fn register(&mut self, def: NodeTypeDef) {
+        match self.types.iter_mut().find(|t| t.name == def.name) {
+            Some(existing) => *existing = def,
+            None => self.types.push(def),
+        }
+    }
+
+    /// 부모 타입이 자식 타입을 가질 수 있는지
+    pub fn allows_child(&self, parent: &NodeType, child: &NodeType) -> bool {
+        self.get(parent)
+            .is_some_and(|def| def.allowed_children.iter().any(|c| c == child.name()))
+    }
+
+    pub fn valid_children(&self, parent: &NodeType) -> Vec<NodeType> {
+        self.get(parent)
+            .map(|def| def.allowed_children.iter().map(|c| NodeType::from_name(c)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn emoji<'a>(&'a self, node_type: &NodeType) -> &'a str {
+        self.get(node_type).map(|def| def.emoji.as_str()).unwrap_or(FALLBACK_EMOJI)
+    }
+
+    pub fn color(&self, node_type: &NodeType) -> [u8; 4] {
+        self.get(node_type).map(|def| def.color).unwrap_or(FALLBACK_COLOR)
+    }
+
+    pub fn default_layer(&self, node_type: &NodeType) -> usize {
+        self.get(node_type).map(|def| def.default_layer).unwrap_or(0)
+    }
+
+    pub fn default_size(&self, node_type: &NodeType) -> f32 {
+        self.get(node_type).map(|def| def.default_size).unwrap_or(FALLBACK_SIZE)
+    }
+}
+
+/// 관계 타입 하나의 정의
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelationTypeDef {
+    pub name: String,
+    /// 비어있으면 이름을 그대로 표시
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default = "fallback_relation_color")]
+    pub color: [u8; 4],
+    #[serde(default = "fallback_thickness")]
+    pub thickness: f32,
+    /// false면 A → B와 B → A를 같은 관계로 본다
+    #[serde(default = "default_directed")]
+    pub directed: bool,
+    /// 시작 노드로 쓸 수 있는 타입 이름들 (비어있으면 모두)
+    #[serde(default)]
+    pub allowed_sources: Vec<String>,
+    /// 끝 노드로 쓸 수 있는 타입 이름들 (비어있으면 모두)
+    #[serde(default)]
+    pub allowed_targets: Vec<String>,
+}
+
+fn default_directed() -> bool {
+    true
+}
+
+impl RelationTypeDef {
+    pub fn new(name: impl Into<String>, color: [u8; 4], thickness: f32) -> Self {
+        let name = name.into();
+        Self {
+            display_name: name.clone(),
+            name,
+            color,
+            thickness,
+            directed: true,
+            allowed_sources: Vec::new(),
+            allowed_targets: Vec::new(),
+        }
+    }
+
+    pub fn with_display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.display_name = display_name.into();
+        self
+    }
+
+    pub fn undirected(mut self) -> Self {
+        self.directed = false;
+        self
+    }
+
+    pub fn with_sources<'a>(mut self, sources: impl IntoIterator<Item = &'a str>) -> Self {
+        self.allowed_sources = sources.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    pub fn with_targets<'a>(mut self, targets: impl IntoIterator<Item = &'a str>) -> Self {
+        self.allowed_targets = targets.into_iter().map(str::to_string).collect();
+        self
+    }
+
+    pub fn relation_type(&self) -> RelationType {
+        RelationType::from_name(&self.name)
+    }
+
+    /// source → target 타입 조합이 허용되는지
+    pub fn allows(&self, source: &NodeType, target: &NodeType) -> bool {
+        let fits = |allowed: &[String], node_type: &NodeType| {
+            allowed.is_empty() || allowed.iter().any(|t| t == node_type.name())
+        };
+        if fits(&self.allowed_sources, source) && fits(&self.allowed_targets, target) {
+            return true;
+        }
+        // 방향이 없는 관계는 반대로 이어도 됨
+        !self.directed && fits(&self.allowed_sources, target) && fits(&self.allowed_targets, source)
+    }
+}
+
+/// 유니버스에서 쓰는 관계 타입들 (유니버스와 함께 저장)
+///
+/// 등록되지 않은 기본 관계 타입은 기본 프리셋의 정의를 쓴다.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelationTypeRegistry {
+    types: Vec<RelationTypeDef>,
+}
+
+// 등록되지 않은 관계 타입에 쓰는 값 (스키마 파일에서 빠진 필드에도 씀)
+const FALLBACK_RELATION_COLOR: [u8; 4] = [255, 255, 255, 255];
+const FALLBACK_THICKNESS: f32 = 1.5;
+
+fn fallback_relation_color() -> [u8; 4] {
+    FALLBACK_RELATION_COLOR
+}
+
+fn fallback_thickness() -> f32 {
+    FALLBACK_THICKNESS
+}
+
+impl Default for RelationTypeRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl RelationTypeRegistry {
+    /// 타입이 하나도 없는 레지스트리
+    pub fn empty() -> Self {
+        Self { types: Vec::new() }
+    }
+
+    /// 기존 RelationType 값들로 만든 기본 프리셋 (모두 방향 있음, 타입 제한 없음)
+    pub fn builtin() -> Self {
+        let types = vec![
+            // 3D 케이크 구조용 관계들
+            RelationTypeDef::new("Parent", [255, 204, 0, 255], 3.0),
+            RelationTypeDef::new("Child", [204, 255, 0, 255], 3.0),
+            RelationTypeDef::new("Sibling", [0, 204, 255, 255], 1.5),
+            // 기존 우주 테마 관계들
+            RelationTypeDef::new("Orbit", [153, 204, 255, 255], 1.5),
+            RelationTypeDef::new("Evolution", [153, 255, 153, 255], 2.0),
+            RelationTypeDef::new("Reference", [255, 153, 153, 255], 1.5),
+            RelationTypeDef::new("Hierarchy", [204, 204, 204, 255], 2.5),
+            // 추가 관계 타입들
+            RelationTypeDef::new("Dependency", [255, 153, 0, 255], 2.0),
+            RelationTypeDef::new("Association", [204, 153, 255, 255], 1.5),
+        ];
+        Self { types }
+    }
+
+    /// 프로그램 전체에서 공유하는 기본 프리셋
+    pub fn builtin_ref() -> &'static RelationTypeRegistry {
+        static BUILTIN: OnceLock<RelationTypeRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(RelationTypeRegistry::builtin)
+    }
+
+    /// 관계 타입 정의 가져오기 (등록되지 않았으면 None)
+    pub fn get(&self, relation_type: &RelationType) -> Option<&RelationTypeDef> {
+        self.get_by_name(relation_type.name())
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&RelationTypeDef> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// 등록된 관계 타입들 (등록한 순서)
+    pub fn types(&self) -> impl Iterator<Item = &RelationTypeDef> {
+        self.types.iter()
+    }
+
+    pub fn contains(&self, relation_type: &RelationType) -> bool {
+        self.get(relation_type).is_some()
+    }
+
+    /// 관계 타입 등록 (같은 이름이 있으면 교체)
+    pub fn register(&mut self, def: RelationTypeDef) {
+        match self.types.iter_mut().find(|t| t.name == def.name) {
+            Some(existing) => *existing = def,
+            None => self.types.push(def),
+        }
+    }
+
+    pub fn display_name<'a>(&'a self, relation_type: &'a RelationType) -> &'a str {
+        self.get(relation_type)
+            .map(|def| def.display_name.as_str())
+            .filter(|name| !name.is_empty())
+            .unwrap_or(relation_type.name())
+    }
+
+    pub fn color(&self, relation_type: &RelationType) -> [u8; 4] {
+        self.get(relation_type).map(|def| def.color).unwrap_or(FALLBACK_RELATION_COLOR)
+    }
+
+    pub fn thickness(&self, relation_type: &RelationType) -> f32 {
+        self.get(relation_type).map(|def| def.thickness).unwrap_or(FALLBACK_THICKNESS)
+    }
+
+    pub fn is_directed(&self, relation_type: &RelationType) -> bool {
+        self.get(relation_type).is_none_or(|def| def.directed)
+    }
+}
+
+impl Graph {
+    /// 이 그래프에서 쓰는 노드 타입들
+    pub fn node_types(&self) -> &NodeTypeRegistry {
+        &self.node_types
+    }
+
+    /// 노드 타입 등록 (같은 이름이 있으면 교체)
+    pub fn register_node_type(&mut self, def: NodeTypeDef) -> GraphResult<()> {
+        let name = def.name.trim();
+        if name.is_empty() || name != def.name {
+            return Err(GraphError::InvalidNodeType(def.name));
+        }
+        self.record_rules();
+        self.node_types.register(def);
+        self.events.emit(GraphEvent::RulesChanged);
+        Ok(())
+    }
+
+    /// parent 타입이 child 타입을 자식으로 가질 수 있게 허용
+    pub fn allow_child_type(&mut self, parent: &NodeType, child: &NodeType) -> GraphResult<()> {
+        let mut def = self.node_types.get(parent)
+            .cloned()
+            .ok_or_else(|| GraphError::InvalidNodeType(parent.name().to_string()))?;
+        if !self.node_types.contains(child) {
+            return Err(GraphError::InvalidNodeType(child.name().to_string()));
+        }
+        if !def.allowed_children.iter().any(|c| c == child.name()) {
+            def.allowed_children.push(child.name().to_string());
+            self.record_rules();
+            self.node_types.register(def);
+            self.events.emit(GraphEvent::RulesChanged);
+        }
+        Ok(())
+    }
+
+    /// 타입 정의의 기본 층/크기/색을 새 노드에 적용
+    pub(crate) fn apply_type_defaults(&self, node: &mut Node, with_layer: bool) {
+        let types = &self.node_types;
+        if with_layer {
+            let layer = types.default_layer(&node.node_type);
+            node.layer = layer;
+            node.position_3d.z = layer as f32 * 100.0;
+            node.layer_radius = 100.0 + layer as f32 * 50.0;
+        }
+        let [r, g, b, a] = types.color(&node.node_type);
+        node.color = [r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0];
+        node.size = types.default_size(&node.node_type);
+    }
+
+    /// 이 그래프에서 쓰는 관계 타입들
+    pub fn relation_types(&self) -> &RelationTypeRegistry {
+        &self.relation_types
+    }
+
+    /// 관계 타입 등록 (같은 이름이 있으면 교체)
+    pub fn register_relation_type(&mut self, def: RelationTypeDef) -> GraphResult<()> {
+        let name = def.name.trim();
+        if name.is_empty() || name != def.name {
+            return Err(GraphError::InvalidRelationType(def.name));
+        }
+        self.record_rules();
+        self.relation_types.register(def);
+        self.events.emit(GraphEvent::RulesChanged);
+        Ok(())
+    }
+
+    /// 새 관계가 관계 타입 정의(등록 여부, 노드 타입 제한)를 지키는지 확인
+    pub(crate) fn check_relation_type(&self, relation: &Relation) -> GraphResult<()> {
+        let def = self.relation_types.get(&relation.relation_type)
+            .ok_or_else(|| GraphError::InvalidRelationType(relation.relation_type.name().to_string()))?;
+        let source = self.require_node(&relation.source_id)?;
+        let target = self.require_node(&relation.target_id)?;
+        if def.allows(&source.node_type, &target.node_type) {
+            return Ok(());
+        }
+        Err(GraphError::RelationNotAllowed {
+            relation_type: relation.relation_type.clone(),
+            source_type: source.node_type.clone(),
+            target_type: target.node_type.clone(),
+        })
+    }
+}
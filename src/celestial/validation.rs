@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use serde::{Serialize, Deserialize};
+use super::{Graph, GraphError, GraphResult, NodeType, Relation};
+
+/// 규칙 위반을 어떻게 처리할지
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnforcementMode {
+    /// 위반하는 편집을 거부
+    #[default]
+    Strict,
+    /// 경고만 남기고 편집은 허용 (`Graph::take_warnings`)
+    Warn,
+    /// 검사하지 않음
+    Off,
+}
+
+/// 부모/자식 타입 규칙을 어긴 계층 연결
+#[derive(Clone, Debug, PartialEq)]
+pub struct HierarchyViolation {
+    /// 위반한 관계 (parent_id 필드로만 연결된 경우 None)
+    pub relation_id: Option<String>,
+    pub parent_id: String,
+    pub child_id: String,
+    pub parent_type: NodeType,
+    pub child_type: NodeType,
+}
+
+impl HierarchyViolation {
+    pub fn to_error(&self) -> GraphError {
+        GraphError::InvalidHierarchy {
+            parent_type: self.parent_type.clone(),
+            child_type: self.child_type.clone(),
+        }
+    }
+}
+
+impl Graph {
+    /// 새 관계가 계층 규칙을 지키는지 검사 (모드에 따라 거부/경고)
+    pub(crate) fn check_hierarchy(&mut self, relation: &Relation) -> GraphResult<()> {
+        if self.hierarchy_mode() == EnforcementMode::Off {
+            return Ok(());
+        }
+        let Some((parent_id, child_id)) = relation.parent_and_child() else {
+            return Ok(());
+        };
+
+        match self.hierarchy_violation(Some(relation.id.clone()), parent_id, child_id) {
+            Some(violation) => self.enforce(self.hierarchy_mode(), violation.to_error()),
+            None => Ok(()),
+        }
+    }
+
+    /// 규칙 위반을 모드에 따라 거부하거나 경고로 남기기 (같은 경고는 한 번만)
+    pub(super) fn enforce(&mut self, mode: EnforcementMode, error: GraphError) -> GraphResult<()> {
+        match mode {
+            EnforcementMode::Strict => Err(error),
+            EnforcementMode::Warn => {
+                if !self.warnings.contains(&error) {
+                    self.warnings.push(error);
+                }
+                Ok(())
+            }
+            EnforcementMode::Off => Ok(()),
+        }
+    }
+
+    /// Warn 모드라서 허용된 규칙 위반들을 일어난 순서대로 꺼내기
+    pub fn take_warnings(&mut self) -> Vec<GraphError> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// 부모 타입이 자식 타입을 가질 수 없으면 위반 정보 반환
+    fn hierarchy_violation(&self, relation_id: Option<String>, parent_id: &str, child_id: &str) -> Option<HierarchyViolation> {
+        let parent = self.get_node(parent_id)?;
+        let child = self.get_node(child_id)?;
+
+        if self.node_types().allows_child(&parent.node_type, &child.node_type) {
+            return None;
+        }
+
+        Some(HierarchyViolation {
+            relation_id,
+            parent_id: parent_id.to_string(),
+            child_id: child_id.to_string(),
+            parent_type: parent.node_type.clone(),
+            child_type: child.node_type.clone(),
+        })
+    }
+
+    /// 그래프에 이미 있는 모든 계층 규칙 위반 찾기
+    pub fn validate(&self) -> Vec<HierarchyViolation> {
+        let mut violations = Vec::new();
+        let mut covered = HashSet::new();
+
+        for relation in self.get_relations() {
+            if let Some((parent_id, child_id)) = relation.parent_and_child() {
+                covered.insert((parent_id, child_id));
+                violations.extend(self.hierarchy_violation(Some(relation.id.clone()), parent_id, child_id));
+            }
+        }
+
+        // 관계 없이 parent_id 필드로만 연결된 노드들
+        for node in self.get_nodes() {
+            if let Some(parent_id) = node.parent_id.as_deref() {
+                if !covered.contains(&(parent_id, node.id.as_str())) {
+                    violations.extend(self.hierarchy_violation(None, parent_id, &node.id));
+                }
+            }
+        }
+
+        violations
+    }
+}
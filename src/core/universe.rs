@@ -1,5 +1,5 @@
 use serde::{Serialize, Deserialize};
-use crate::celestial::Graph;
+use crate::celestial::{Graph, Position2D};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Universe {
@@ -16,6 +16,41 @@ impl Universe {
             graph,
         }
     }
+
+    /// 케이크 밖에 있는 노드들(케이크가 없던 시절의 유니버스)을 유니버스 제목의 케이크 하나로 모은 그래프
+    ///
+    /// 케이크 중심은 노드 위치들의 가운데이고, 각 노드는 중심에서의 거리/각도로 원래 배치를 유지한다.
+    pub fn into_cake_graph(self) -> Graph {
+        let Universe { title, mut graph, .. } = self;
+        let loose: Vec<(String, Position2D)> = graph.get_nodes()
+            .filter(|n| n.cake_id.is_none())
+            .map(|n| (n.id.clone(), n.position))
+            .collect();
+        if loose.is_empty() {
+            return graph;
+        }
+
+        let count = loose.len() as f32;
+        let center = Position2D::new(
+            loose.iter().map(|(_, p)| p.x).sum::<f32>() / count,
+            loose.iter().map(|(_, p)| p.y).sum::<f32>() / count,
+        );
+        let cake_id = graph.create_cake(title, center);
+
+        for (node_id, position) in loose {
+            let (dx, dy) = (position.x - center.x, position.y - center.y);
+            let Some(mut node) = graph.get_node_mut(&node_id) else {
+                continue;
+            };
+            node.cake_id = Some(cake_id.clone());
+            node.layer_radius = dx.hypot(dy);
+            node.layer_angle = dy.atan2(dx);
+            let layer = node.layer;
+            drop(node);
+            let _ = graph.expand_cake(&cake_id, layer);
+        }
+        graph
+    }
 }
 
 impl From<Graph> for Universe {
@@ -27,9 +62,3 @@ impl From<Graph> for Universe {
         }
     }
 }
-
-impl From<Universe> for Graph {
-    fn from(universe: Universe) -> Self {
-        universe.graph
-    }
-} 
\ No newline at end of file
@@ -1,152 +1,187 @@
 use eframe::egui;
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeSet;
+use chrono::Datelike;
+use cosmos::celestial::{Cake, CakeScope, DeletePolicy, EditHistory, Graph, GraphError, Journal, JournalEntry, Node, NodeType, normalize_tag, NodeTypeDef, Position2D, Properties, PropertyValue, RelationType, RelationTypeDef, UniverseSchema};
+use cosmos::storage::{BackupInfo, Storage, StorageError, StorageResult, UniverseInfo, WorkspaceInfo};
 
-// 3D 케이크 구조의 노드
-#[derive(Clone, Debug)]
-struct CakeNode {
-    id: String,
-    title: String,
-    layer: usize,
-    angle: f32,
-    radius: f32,
-    node_type: NodeType,
-    cake_id: String,  // 어느 케이크에 속하는지
-    selected: bool,
+// 자동 저장 간격 (초)
+const AUTOSAVE_INTERVAL_SECS: f64 = 30.0;
+// 이름 없는 워크스페이스의 자동 저장 ID
+const AUTOSAVE_ID: &str = "autosave";
+// 저널이 이만큼 쌓이면 워크스페이스 파일을 새로 쓰고 저널을 비움
+const JOURNAL_COMPACT_THRESHOLD: usize = 500;
+
+// 노드 타입별 화면 색상 (그래프의 타입 정의 기준)
+fn node_type_color(graph: &Graph, node_type: &NodeType) -> egui::Color32 {
+    let [r, g, b, a] = graph.node_types().color(node_type);
+    egui::Color32::from_rgba_unmultiplied(r, g, b, a)
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum NodeType {
-    Root,    // 🌌
-    Concept, // 💭
-    Task,    // 📋
-    Note,    // 📝
+// 연결 메뉴에 보여줄 관계 타입들 (자주 쓰는 기본 타입 + 직접 정의한 타입)
+fn selectable_relation_types(graph: &Graph) -> Vec<RelationType> {
+    let mut types = vec![
+        RelationType::Association,
+        RelationType::Dependency,
+        RelationType::Reference,
+        RelationType::Hierarchy,
+    ];
+    types.extend(graph.relation_types().types()
+        .map(|def| def.relation_type())
+        .filter(|t| !t.is_builtin()));
+    types
 }
 
-impl NodeType {
-    fn emoji(&self) -> &'static str {
-        match self {
-            NodeType::Root => "🌌",
-            NodeType::Concept => "💭",
-            NodeType::Task => "📋",
-            NodeType::Note => "📝",
-        }
-    }
+// 생성 메뉴와 타입 창에 보여줄 타입들 (케이크 기본 타입 + 직접 정의한 타입)
+fn selectable_node_types(graph: &Graph) -> Vec<NodeType> {
+    let mut types = vec![NodeType::Root, NodeType::Concept, NodeType::Task, NodeType::Note];
+    types.extend(graph.node_types().types()
+        .map(|def| def.node_type())
+        .filter(|t| !t.is_builtin()));
+    types
+}
 
-    fn color(&self) -> egui::Color32 {
-        match self {
-            NodeType::Root => egui::Color32::from_rgb(255, 215, 0),   // 골드
-            NodeType::Concept => egui::Color32::from_rgb(100, 149, 237), // 블루
-            NodeType::Task => egui::Color32::from_rgb(255, 105, 180), // 핑크
-            NodeType::Note => egui::Color32::from_rgb(50, 205, 50),   // 그린
+// 케이크 테마별 레이어 색상
+fn cake_layer_color(cake: &Cake, layer: usize) -> egui::Color32 {
+    match cake.color_theme {
+        0 => match layer { // 기본 테마
+            0 => egui::Color32::from_rgba_unmultiplied(255, 215, 0, 120),
+            1 => egui::Color32::from_rgba_unmultiplied(173, 216, 230, 120),
+            2 => egui::Color32::from_rgba_unmultiplied(255, 182, 193, 120),
+            3 => egui::Color32::from_rgba_unmultiplied(144, 238, 144, 120),
+            4 => egui::Color32::from_rgba_unmultiplied(255, 160, 122, 120),
+            5 => egui::Color32::from_rgba_unmultiplied(221, 160, 221, 120),
+            _ => egui::Color32::from_rgba_unmultiplied(200, 200, 200, 120),
+        },
+        1 => match layer { // 따뜻한 테마
+            0 => egui::Color32::from_rgba_unmultiplied(255, 140, 0, 120),
+            1 => egui::Color32::from_rgba_unmultiplied(255, 160, 122, 120),
+            2 => egui::Color32::from_rgba_unmultiplied(255, 192, 203, 120),
+            3 => egui::Color32::from_rgba_unmultiplied(255, 218, 185, 120),
+            4 => egui::Color32::from_rgba_unmultiplied(255, 228, 196, 120),
+            5 => egui::Color32::from_rgba_unmultiplied(255, 239, 213, 120),
+            _ => egui::Color32::from_rgba_unmultiplied(255, 245, 230, 120),
+        },
+        2 => match layer { // 차가운 테마
+            0 => egui::Color32::from_rgba_unmultiplied(70, 130, 180, 120),
+            1 => egui::Color32::from_rgba_unmultiplied(135, 206, 250, 120),
+            2 => egui::Color32::from_rgba_unmultiplied(173, 216, 230, 120),
+            3 => egui::Color32::from_rgba_unmultiplied(224, 255, 255, 120),
+            4 => egui::Color32::from_rgba_unmultiplied(240, 248, 255, 120),
+            5 => egui::Color32::from_rgba_unmultiplied(248, 248, 255, 120),
+            _ => egui::Color32::from_rgba_unmultiplied(250, 250, 255, 120),
+        },
+        _ => match layer { // 자연 테마
+            0 => egui::Color32::from_rgba_unmultiplied(139, 69, 19, 120),
+            1 => egui::Color32::from_rgba_unmultiplied(34, 139, 34, 120),
+            2 => egui::Color32::from_rgba_unmultiplied(154, 205, 50, 120),
+            3 => egui::Color32::from_rgba_unmultiplied(240, 230, 140, 120),
+            4 => egui::Color32::from_rgba_unmultiplied(255, 255, 224, 120),
+            5 => egui::Color32::from_rgba_unmultiplied(250, 240, 230, 120),
+            _ => egui::Color32::from_rgba_unmultiplied(245, 245, 220, 120),
         }
     }
+}
 
-    fn layer(&self) -> usize {
-        match self {
-            NodeType::Root => 0,
-            NodeType::Concept => 1,
-            NodeType::Task => 2,
-            NodeType::Note => 3,
-        }
+// 케이크 중심의 캔버스 좌표
+fn cake_center(cake: &Cake) -> egui::Pos2 {
+    egui::Pos2::new(cake.center.x, cake.center.y)
+}
+
+// 속성 추가 창에서 고를 수 있는 값 타입들
+const PROPERTY_TYPES: [&str; 6] = ["String", "Number", "Bool", "Date", "List", "Node"];
+
+fn empty_property(type_name: &str, graph: &Graph) -> PropertyValue {
+    match type_name {
+        "Number" => PropertyValue::Number(0.0),
+        "Bool" => PropertyValue::Bool(false),
+        "Date" => PropertyValue::Date(chrono::Utc::now().date_naive()),
+        "List" => PropertyValue::List(Vec::new()),
+        "Node" => PropertyValue::NodeRef(graph.get_nodes().next().map(|n| n.id.clone()).unwrap_or_default()),
+        _ => PropertyValue::String(String::new()),
     }
 }
 
-// 케이크 구조 정의
-#[derive(Clone, Debug)]
-struct CakeStructure {
-    id: String,
-    title: String,
-    center: egui::Pos2,
-    size_scale: f32,
-    color_theme: usize, // 0: 기본, 1: 따뜻한톤, 2: 차가운톤, 3: 자연톤
-    max_layer: usize,   // 동적 레이어 관리
+// 노드 타입 이름들을 고르는 체크박스 묶음
+fn node_type_checkboxes(ui: &mut egui::Ui, types: &[NodeType], selected: &mut BTreeSet<String>) {
+    ui.horizontal_wrapped(|ui| {
+        for node_type in types {
+            let name = node_type.name().to_string();
+            let mut checked = selected.contains(&name);
+            if ui.checkbox(&mut checked, &name).changed() {
+                if checked {
+                    selected.insert(name);
+                } else {
+                    selected.remove(&name);
+                }
+            }
+        }
+    });
 }
 
-impl CakeStructure {
-    fn new(id: String, title: String, center: egui::Pos2) -> Self {
-        Self {
-            id,
-            title,
-            center,
-            size_scale: 1.0,
-            color_theme: 0,
-            max_layer: 0,  // 기본 1레이어(0부터 시작)
-        }
-    }
-
-    fn get_layer_color(&self, layer: usize) -> egui::Color32 {
-        match self.color_theme {
-            0 => match layer { // 기본 테마
-                0 => egui::Color32::from_rgba_unmultiplied(255, 215, 0, 120),
-                1 => egui::Color32::from_rgba_unmultiplied(173, 216, 230, 120),
-                2 => egui::Color32::from_rgba_unmultiplied(255, 182, 193, 120),
-                3 => egui::Color32::from_rgba_unmultiplied(144, 238, 144, 120),
-                4 => egui::Color32::from_rgba_unmultiplied(255, 160, 122, 120),
-                5 => egui::Color32::from_rgba_unmultiplied(221, 160, 221, 120),
-                _ => egui::Color32::from_rgba_unmultiplied(200, 200, 200, 120),
-            },
-            1 => match layer { // 따뜻한 테마
-                0 => egui::Color32::from_rgba_unmultiplied(255, 140, 0, 120),
-                1 => egui::Color32::from_rgba_unmultiplied(255, 160, 122, 120),
-                2 => egui::Color32::from_rgba_unmultiplied(255, 192, 203, 120),
-                3 => egui::Color32::from_rgba_unmultiplied(255, 218, 185, 120),
-                4 => egui::Color32::from_rgba_unmultiplied(255, 228, 196, 120),
-                5 => egui::Color32::from_rgba_unmultiplied(255, 239, 213, 120),
-                _ => egui::Color32::from_rgba_unmultiplied(255, 245, 230, 120),
-            },
-            2 => match layer { // 차가운 테마
-                0 => egui::Color32::from_rgba_unmultiplied(70, 130, 180, 120),
-                1 => egui::Color32::from_rgba_unmultiplied(135, 206, 250, 120),
-                2 => egui::Color32::from_rgba_unmultiplied(173, 216, 230, 120),
-                3 => egui::Color32::from_rgba_unmultiplied(224, 255, 255, 120),
-                4 => egui::Color32::from_rgba_unmultiplied(240, 248, 255, 120),
-                5 => egui::Color32::from_rgba_unmultiplied(248, 248, 255, 120),
-                _ => egui::Color32::from_rgba_unmultiplied(250, 250, 255, 120),
-            },
-            _ => match layer { // 자연 테마
-                0 => egui::Color32::from_rgba_unmultiplied(139, 69, 19, 120),
-                1 => egui::Color32::from_rgba_unmultiplied(34, 139, 34, 120),
-                2 => egui::Color32::from_rgba_unmultiplied(154, 205, 50, 120),
-                3 => egui::Color32::from_rgba_unmultiplied(240, 230, 140, 120),
-                4 => egui::Color32::from_rgba_unmultiplied(255, 255, 224, 120),
-                5 => egui::Color32::from_rgba_unmultiplied(250, 240, 230, 120),
-                _ => egui::Color32::from_rgba_unmultiplied(245, 245, 220, 120),
+// 속성 값 편집 위젯 (리스트는 항목마다 다시 호출)
+fn edit_property_value(ui: &mut egui::Ui, value: &mut PropertyValue, graph: &Graph, id_source: &str) {
+    match value {
+        PropertyValue::String(text) => {
+            ui.text_edit_singleline(text);
+        }
+        PropertyValue::Number(number) => {
+            ui.add(egui::DragValue::new(number).speed(0.1));
+        }
+        PropertyValue::Bool(flag) => {
+            ui.checkbox(flag, "");
+        }
+        PropertyValue::Date(date) => {
+            let (mut year, mut month, mut day) = (date.year(), date.month(), date.day());
+            ui.add(egui::DragValue::new(&mut year).clamp_range(1..=9999));
+            ui.add(egui::DragValue::new(&mut month).clamp_range(1..=12));
+            ui.add(egui::DragValue::new(&mut day).clamp_range(1..=31));
+            if let Some(new_date) = chrono::NaiveDate::from_ymd_opt(year, month, day) {
+                *date = new_date;
             }
         }
-    }
-    
-    // 레이어 확장
-    fn expand_to_layer(&mut self, layer: usize) {
-        if layer > self.max_layer {
-            self.max_layer = layer;
+        PropertyValue::List(items) => {
+            ui.vertical(|ui| {
+                let mut removed = None;
+                for (i, item) in items.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        edit_property_value(ui, item, graph, &format!("{}_{}", id_source, i));
+                        if ui.small_button("✖").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = removed {
+                    items.remove(i);
+                }
+                if ui.small_button("+ Item").clicked() {
+                    items.push(PropertyValue::String(String::new()));
+                }
+            });
+        }
+        PropertyValue::NodeRef(node_id) => {
+            let selected = graph.get_node(node_id).map(|n| n.title.as_str()).unwrap_or("(missing)");
+            egui::ComboBox::from_id_source(id_source)
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for node in graph.get_nodes() {
+                        ui.selectable_value(node_id, node.id.clone(), &node.title);
+                    }
+                });
         }
     }
 }
 
-// 연결선 (케이크 간 연결 포함)
-#[derive(Clone, Debug)]
-struct Connection {
-    from_id: String,
-    to_id: String,
-    connection_type: ConnectionType,
-}
-
-#[derive(Clone, Debug, PartialEq)]
-enum ConnectionType {
-    IntraCake,  // 케이크 내부 연결
-    InterCake,  // 케이크 간 연결
-}
-
 #[derive(Debug)]
 enum InteractionMode {
     None,
-    DraggingNode(String),
     CreatingConnection(String),  // 드래그로 연결 생성
     DraggingCake(String),
     PanningView,  // 두 손가락 팬
 }
 
 // 뷰포트 관리 구조체
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct Viewport {
     offset: egui::Vec2,
     zoom: f32,
@@ -175,12 +210,27 @@ impl Viewport {
     }
 }
 
+// 디스크에 저장되는 케이크 워크스페이스 전체
+#[derive(Serialize, Deserialize)]
+struct CakeWorkspace {
+    graph: Graph,  // 케이크, 노드, 연결 모두 포함
+    viewport: Viewport,
+}
+
+// 저장할 때 그래프를 복사하지 않고 빌려서 쓰는 CakeWorkspace (같은 형식으로 저장됨)
+#[derive(Serialize)]
+struct CakeWorkspaceRef<'a> {
+    graph: &'a Graph,
+    viewport: &'a Viewport,
+}
+
+// 열기 창에 보여줄 워크스페이스 목록과 유니버스 목록
+type OpenListing = (StorageResult<Vec<WorkspaceInfo>>, StorageResult<Vec<UniverseInfo>>);
+
 // 다차원 케이크 그래프 앱
 struct Cosmos3DApp {
     // 케이크 구조들
-    cakes: Vec<CakeStructure>,
-    nodes: Vec<CakeNode>,
-    connections: Vec<Connection>,
+    graph: Graph,  // 케이크, 노드, 연결은 라이브러리 그래프가 소유
     
     // 뷰포트 및 네비게이션
     viewport: Viewport,
@@ -205,21 +255,85 @@ struct Cosmos3DApp {
     new_cake_theme: usize,
     
     // 편집 상태
+    history: EditHistory,  // 실행 취소/다시 실행 기록
     editing_node: Option<String>,
     edit_title: String,
+    edit_properties: Properties,
+    new_property_key: String,
+    new_property_type: usize,
+    edit_tags: String,  // 쉼표로 구분한 태그들
     
     // 뷰 설정
     show_layers: bool,
     show_connections: bool,
     show_cake_titles: bool,
+    show_task_order: bool,
+    new_relation_type: RelationType,  // 드래그로 만드는 연결의 타입
+    delete_policy: DeletePolicy,      // 노드 삭제 시 자식 처리 방법
+    tag_filter: Option<String>,       // 이 태그가 없는 노드는 흐리게
+    show_tag_manager: bool,
+    rename_tag_from: String,
+    rename_tag_to: String,
+    show_type_editor: bool,
+    new_type_name: String,
+    new_type_emoji: String,
+    new_type_layer: usize,
+    new_type_color: egui::Color32,
+    new_type_children: BTreeSet<String>,  // 새 타입이 가질 수 있는 자식 타입들
+    new_type_parents: BTreeSet<String>,   // 새 타입을 자식으로 가질 수 있는 타입들
+    new_relation_name: String,
+    new_relation_color: egui::Color32,
+    new_relation_thickness: f32,
+    new_relation_directed: bool,
+    new_relation_sources: BTreeSet<String>,  // 비어있으면 모든 노드 타입
+    new_relation_targets: BTreeSet<String>,
+    schema_path: String,  // 불러오거나 내보낼 스키마 파일 경로
+    
+    // 저장/불러오기
+    storage: Option<Storage>,  // None이면 쓸 수 있는 디렉토리가 없어 메모리에서만 작업
+    current_workspace: Option<String>,  // None이면 이름 없는 워크스페이스
+    has_unsaved_changes: bool,
+    last_autosave: f64,
+    show_open_dialog: bool,
+    show_save_as_dialog: bool,
+    show_backups: bool,
+    // 창에 보여줄 목록 (매 프레임 디렉토리를 읽지 않도록 창을 열 때와 Refresh 때만 읽음)
+    open_listing: Option<OpenListing>,
+    backup_listing: Option<(String, StorageResult<Vec<BackupInfo>>)>,  // 워크스페이스 ID와 그 백업들
+    universe_backup_listing: Option<(String, StorageResult<Vec<BackupInfo>>)>,  // 열어 둔 유니버스 ID와 그 백업들
+    opened_universe: Option<String>,  // 지금 그래프를 불러온 예전 유니버스 파일 (워크스페이스를 열면 None)
+    save_as_name: String,
+    status_message: String,
+    storage_warnings: Vec<StorageError>,  // 작업은 계속했지만 상태 표시줄로 알릴 저장소 문제 (건너뛴 파일/저널 줄 등)
+
+    // 변경 저널 (자동 저장은 전체 파일 대신 저널에 덧붙임)
+    journal: Journal,
+    journal_pending: Vec<JournalEntry>,  // 아직 파일에 쓰지 않은 변경
+    journal_len: usize,                  // 마지막 스냅샷 이후 저널에 쓴 항목 수
+    journal_base: Option<String>,        // 저널이 이어 붙는 스냅샷(워크스페이스 파일)의 ID
+    node_journal: Option<(String, Vec<String>)>,  // 편집 창에 보여줄 노드의 변경 기록
 }
 
 impl Cosmos3DApp {
     fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+        // 데이터 디렉토리를 쓸 수 없으면 임시 디렉토리에 저장하고, 그것도 안 되면 저장 없이 작업하도록 하고 사용자에게 알림
+        let (storage, storage_error) = match Storage::new() {
+            Ok(storage) => (Some(storage), None),
+            Err(e) => match Storage::with_dir(std::env::temp_dir().join("cosmos")) {
+                Ok(storage) => {
+                    let message = format!("Cannot use data directory ({}), saving to {}", e, storage.data_dir().display());
+                    (Some(storage), Some(message))
+                }
+                Err(temp_error) => {
+                    let message = format!("Cannot use data directory ({}) or {}, changes will not be saved", e, temp_error);
+                    (None, Some(message))
+                }
+            },
+        };
+        let mut graph = Graph::new();
+        let journal = Journal::attach(&mut graph);
         let mut app = Self {
-            cakes: Vec::new(),
-            nodes: Vec::new(),
-            connections: Vec::new(),
+            graph,
             viewport: Viewport::default(),
             selected_node: None,
             selected_cake: None,
@@ -232,84 +346,523 @@ impl Cosmos3DApp {
             show_cake_creator: false,
             new_cake_title: String::new(),
             new_cake_theme: 0,
+            history: EditHistory::new(),
             editing_node: None,
             edit_title: String::new(),
+            edit_properties: Properties::new(),
+            new_property_key: String::new(),
+            new_property_type: 0,
+            edit_tags: String::new(),
             show_layers: true,
             show_connections: true,
             show_cake_titles: true,
+            show_task_order: false,
+            new_relation_type: RelationType::Association,
+            delete_policy: DeletePolicy::default(),
+            tag_filter: None,
+            show_tag_manager: false,
+            rename_tag_from: String::new(),
+            rename_tag_to: String::new(),
+            show_type_editor: false,
+            new_type_name: String::new(),
+            new_type_emoji: "🔹".to_string(),
+            new_type_layer: 1,
+            new_type_color: egui::Color32::from_rgb(180, 180, 180),
+            new_type_children: BTreeSet::new(),
+            new_type_parents: BTreeSet::new(),
+            new_relation_name: String::new(),
+            new_relation_color: egui::Color32::from_rgb(120, 200, 255),
+            new_relation_thickness: 2.0,
+            new_relation_directed: true,
+            new_relation_sources: BTreeSet::new(),
+            new_relation_targets: BTreeSet::new(),
+            schema_path: String::new(),
+            storage,
+            current_workspace: None,
+            has_unsaved_changes: false,
+            last_autosave: 0.0,
+            show_open_dialog: false,
+            show_save_as_dialog: false,
+            show_backups: false,
+            open_listing: None,
+            backup_listing: None,
+            universe_backup_listing: None,
+            opened_universe: None,
+            save_as_name: String::new(),
+            status_message: String::new(),
+            storage_warnings: Vec::new(),
+            journal,
+            journal_pending: Vec::new(),
+            journal_len: 0,
+            journal_base: None,
+            node_journal: None,
         };
         
-        // 초기 데모 케이크들 생성
-        app.create_demo_structures();
+        // 가장 최근 워크스페이스를 열고, 없으면 데모 케이크들 생성
+        let latest = match app.storage().and_then(Storage::list_workspaces) {
+            Ok(workspaces) => workspaces.first().map(|w| w.id.clone()),
+            Err(e) => {
+                app.status_message = format!("Failed to list workspaces: {}", e);
+                None
+            }
+        };
+        let opened = latest.map(|id| app.open_workspace(&id)).unwrap_or(false);
+        if !opened {
+            app.create_demo_structures();
+            app.history.clear(&mut app.graph);
+            app.restart_journal();
+            app.has_unsaved_changes = false;
+        }
+        if let Some(message) = storage_error {
+            app.status_message = message;
+        }
         app
     }
 
+    fn to_workspace(&self) -> CakeWorkspaceRef<'_> {
+        CakeWorkspaceRef {
+            graph: &self.graph,
+            viewport: &self.viewport,
+        }
+    }
+
+    // 저장소 (쓸 수 있는 디렉토리가 없어 메모리에서만 작업 중이면 오류)
+    fn storage(&self) -> StorageResult<&Storage> {
+        self.storage.as_ref().ok_or(StorageError::Unavailable)
+    }
+
+    fn apply_workspace(&mut self, workspace: CakeWorkspace) {
+        self.graph = workspace.graph;
+        self.viewport = workspace.viewport;
+        
+        // 이전 워크스페이스의 선택/편집 상태와 편집 기록 초기화
+        self.history.clear(&mut self.graph);
+        self.selected_node = None;
+        self.selected_cake = None;
+        self.editing_node = None;
+        self.node_journal = None;
+        self.interaction_mode = InteractionMode::None;
+        self.drag_start_pos = None;
+    }
+
+    // 지금부터의 변경만 기록 (불러오기나 데모 생성은 기록하지 않음)
+    fn restart_journal(&mut self) {
+        self.journal.collect(&mut self.graph);
+        self.journal_pending.clear();
+    }
+
+    fn open_workspace(&mut self, id: &str) -> bool {
+        match self.storage().and_then(|storage| storage.load_workspace::<CakeWorkspace>(id)) {
+            Ok(workspace) => {
+                let journal = match self.storage().and_then(|storage| storage.load_journal(id)) {
+                    Ok((entries, warnings)) => {
+                        self.storage_warnings.extend(warnings);
+                        entries
+                    }
+                    Err(e) => {
+                        self.status_message = format!("Failed to open '{}': {}", id, e);
+                        return false;
+                    }
+                };
+                self.apply_workspace(workspace);
+                self.journal = Journal::attach(&mut self.graph);
+                self.graph.replay(&journal);
+                self.graph.repair_hierarchy();
+                self.restart_journal();
+                self.journal_len = journal.len();
+                self.journal_base = Some(id.to_string());
+                // 자동 저장본은 이름 없는 워크스페이스로 취급
+                self.current_workspace = (id != AUTOSAVE_ID).then(|| id.to_string());
+                self.opened_universe = None;
+                self.has_unsaved_changes = false;
+                let violations = self.graph.validate().len();
+                self.status_message = if violations > 0 {
+                    format!("Opened '{}' ({} hierarchy violations)", id, violations)
+                } else {
+                    format!("Opened '{}'", id)
+                };
+                true
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to open '{}': {}", id, e);
+                false
+            }
+        }
+    }
+
+    // 유니버스 목록의 노드 배치 미리보기 (0~1로 정규화된 위치)
+    fn draw_thumbnail(ui: &mut egui::Ui, points: &[[f32; 2]]) {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(32.0, 32.0), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, egui::Color32::from_gray(30));
+        let inner = rect.shrink(3.0);
+        for [x, y] in points {
+            let pos = inner.min + egui::vec2(x * inner.width(), y * inner.height());
+            painter.circle_filled(pos, 1.5, egui::Color32::LIGHT_BLUE);
+        }
+    }
+
+    // 예전 유니버스 파일을 이름 없는 워크스페이스로 열기 (저장하면 워크스페이스가 된다)
+    fn open_universe(&mut self, id: &str) -> bool {
+        match self.storage().and_then(|storage| storage.load_universe(id)) {
+            Ok(graph) => {
+                self.apply_workspace(CakeWorkspace {
+                    graph,
+                    viewport: Viewport::default(),
+                });
+                self.journal = Journal::attach(&mut self.graph);
+                self.journal_len = 0;
+                self.journal_base = None;
+                self.current_workspace = None;
+                self.opened_universe = Some(id.to_string());
+                self.has_unsaved_changes = true;
+                self.status_message = format!("Opened universe '{}' ({} nodes)", id, self.graph.node_count());
+                true
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to open universe '{}': {}", id, e);
+                false
+            }
+        }
+    }
+
+    // 쌓인 변경을 저널에 쓰고, 필요하면 워크스페이스 파일(스냅샷)을 새로 쓴 뒤 저널 비우기
+    fn flush_journal(&mut self, id: &str, snapshot: bool) -> StorageResult<()> {
+        Journal::append(&mut self.journal_pending, self.journal.collect(&mut self.graph));
+        let mut pending = std::mem::take(&mut self.journal_pending);
+        let snapshot = snapshot
+            || self.journal_base.as_deref() != Some(id)
+            || self.journal_len + pending.len() >= JOURNAL_COMPACT_THRESHOLD
+            || pending.iter().any(|e| e.op.is_snapshot());
+        if snapshot {
+            // 그래프 전체는 워크스페이스 파일에 들어가므로 저널에는 남기지 않는다
+            pending.retain(|e| !e.op.is_snapshot());
+        }
+
+        if let Err(e) = self.storage().and_then(|storage| storage.append_journal(id, &pending)) {
+            self.journal_pending = pending;
+            return Err(e);
+        }
+        self.journal_len += pending.len();
+
+        if snapshot {
+            self.storage()?.save_workspace(&self.to_workspace(), id)?;
+            self.storage()?.compact_journal(id)?;
+            self.journal_len = 0;
+            self.journal_base = Some(id.to_string());
+        }
+        Ok(())
+    }
+
+    fn save_workspace_as(&mut self, id: &str) {
+        match self.flush_journal(id, true) {
+            Ok(()) => {
+                self.current_workspace = Some(id.to_string());
+                self.has_unsaved_changes = false;
+                self.status_message = format!("Saved '{}'", id);
+            }
+            Err(e) => {
+                self.status_message = format!("Failed to save '{}': {}", id, e);
+            }
+        }
+    }
+
+    // 현재 상태를 먼저 저장(백업으로 남음)한 뒤 백업 내용으로 되돌리고 다시 열기
+    fn restore_backup(&mut self, id: &str, name: &str) {
+        if let Err(e) = self.flush_journal(id, true) {
+            self.status_message = format!("Failed to save '{}' before restoring: {}", id, e);
+            return;
+        }
+        match self.storage().and_then(|storage| storage.restore_workspace_backup(id, name)) {
+            Ok(()) => {
+                if self.open_workspace(id) {
+                    self.status_message = format!("Restored '{}' from {}", id, name);
+                }
+            }
+            Err(e) => self.status_message = format!("Failed to restore '{}': {}", id, e),
+        }
+    }
+
+    // 지금 편집 중인 것은 워크스페이스에 먼저 저장해 두고 유니버스 파일을 백업 내용으로 되돌린 뒤 다시 열기
+    fn restore_universe_backup(&mut self, universe_id: &str, name: &str) {
+        let id = self.current_workspace.clone().unwrap_or_else(|| AUTOSAVE_ID.to_string());
+        if let Err(e) = self.flush_journal(&id, true) {
+            self.status_message = format!("Failed to save '{}' before restoring: {}", id, e);
+            return;
+        }
+        match self.storage().and_then(|storage| storage.restore_universe_backup(universe_id, name)) {
+            Ok(()) => {
+                if self.open_universe(universe_id) {
+                    self.status_message = format!("Restored universe '{}' from {}", universe_id, name);
+                }
+                // 목록의 노드 수/미리보기가 바뀌었다
+                self.open_listing = None;
+            }
+            Err(e) => self.status_message = format!("Failed to restore universe '{}': {}", universe_id, e),
+        }
+    }
+
+    // 백업 목록 (누른 백업 이름 반환)
+    fn draw_backup_list(ui: &mut egui::Ui, backups: Option<&StorageResult<Vec<BackupInfo>>>, empty: &str) -> Option<String> {
+        let mut restore = None;
+        match backups {
+            None => {}
+            Some(Ok(backups)) if backups.is_empty() => {
+                ui.label(empty);
+            }
+            Some(Ok(backups)) => {
+                for backup in backups {
+                    ui.horizontal(|ui| {
+                        ui.label(backup.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string());
+                        if ui.button("Restore").clicked() {
+                            restore = Some(backup.name.clone());
+                        }
+                    });
+                }
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::LIGHT_RED, format!("Failed to list backups: {}", e));
+            }
+        }
+        restore
+    }
+
+    fn save_current(&mut self) {
+        if let Some(id) = self.current_workspace.clone() {
+            self.save_workspace_as(&id);
+        } else {
+            self.show_save_as_dialog = true;
+        }
+    }
+
+    fn autosave(&mut self, now: f64, closing: bool) {
+        self.last_autosave = now;
+        // 저장할 곳이 없으면 시작할 때 띄운 안내를 덮어쓰지 않는다
+        if self.storage.is_none() {
+            return;
+        }
+        let id = self.current_workspace.clone().unwrap_or_else(|| AUTOSAVE_ID.to_string());
+        // 평소에는 저널에만 덧붙이고, 창을 닫을 때는 보기 상태까지 전체 저장
+        match self.flush_journal(&id, closing) {
+            Ok(()) => {
+                self.has_unsaved_changes = false;
+                self.status_message = format!("Autosaved '{}'", id);
+            }
+            Err(e) => {
+                self.status_message = format!("Autosave failed: {}", e);
+            }
+        }
+    }
+
+    // 타입 창에서 입력한 타입 등록
+    fn add_node_type(&mut self) {
+        let name = self.new_type_name.trim().to_string();
+        let color = self.new_type_color;
+        let def = NodeTypeDef::new(name.clone(), self.new_type_emoji.trim(), [color.r(), color.g(), color.b(), color.a()], self.new_type_layer)
+            .with_children(self.new_type_children.iter().map(String::as_str));
+        let parents = &self.new_type_parents;
+        let result = self.history.apply(&mut self.graph, "Add node type", |graph| {
+            graph.register_node_type(def)?;
+            let node_type = NodeType::from_name(&name);
+            for parent in parents {
+                graph.allow_child_type(&NodeType::from_name(parent), &node_type)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            self.status_message = format!("Failed to add type: {}", e);
+            return;
+        }
+
+        self.status_message = format!("Added node type '{}'", name);
+        self.has_unsaved_changes = true;
+        self.new_type_name.clear();
+        self.new_type_children.clear();
+        self.new_type_parents.clear();
+    }
+
+    // 타입 창에서 입력한 관계 타입 등록
+    fn add_relation_type(&mut self) {
+        let name = self.new_relation_name.trim().to_string();
+        let color = self.new_relation_color;
+        let mut def = RelationTypeDef::new(name.clone(), [color.r(), color.g(), color.b(), color.a()], self.new_relation_thickness)
+            .with_sources(self.new_relation_sources.iter().map(String::as_str))
+            .with_targets(self.new_relation_targets.iter().map(String::as_str));
+        if !self.new_relation_directed {
+            def = def.undirected();
+        }
+        if let Err(e) = self.history.apply(&mut self.graph, "Add relation type", |graph| graph.register_relation_type(def)) {
+            self.status_message = format!("Failed to add relation type: {}", e);
+            return;
+        }
+
+        self.status_message = format!("Added relation type '{}'", name);
+        self.has_unsaved_changes = true;
+        self.new_relation_name.clear();
+        self.new_relation_sources.clear();
+        self.new_relation_targets.clear();
+    }
+
+    // 스키마 파일의 층과 타입 정의로 교체
+    fn load_schema(&mut self) {
+        let path = self.schema_path.trim().to_string();
+        let result = UniverseSchema::load(&path).and_then(|schema| {
+            self.history.apply(&mut self.graph, "Apply schema", |graph| graph.apply_schema(&schema))?;
+            Ok(schema.name)
+        });
+        match result {
+            Ok(name) => {
+                self.status_message = format!("Applied schema '{}'", name);
+                self.has_unsaved_changes = true;
+            }
+            Err(e) => self.status_message = format!("Failed to load schema: {}", e),
+        }
+    }
+
+    // 현재 층과 타입 정의를 스키마 파일로 저장
+    fn export_schema(&mut self) {
+        let path = self.schema_path.trim().to_string();
+        let name = self.current_workspace.clone().unwrap_or_else(|| "Untitled".to_string());
+        self.status_message = match self.graph.schema(name).save(&path) {
+            Ok(()) => format!("Exported schema to {}", path),
+            Err(e) => format!("Failed to export schema: {}", e),
+        };
+    }
+
+    // 저장된 저널과 아직 쓰지 않은 변경에서 노드 관련 항목만 (최근 것이 위)
+    fn node_journal_lines(&self, node_id: &str) -> Vec<String> {
+        let (mut entries, errors) = match self.journal_base.as_deref().map(|id| self.storage()?.load_journal_history(id)) {
+            Some(Ok((entries, warnings))) => (entries, warnings.iter().map(|e| format!("Skipped journal line: {}", e)).collect()),
+            Some(Err(e)) => (Vec::new(), vec![format!("Failed to read journal: {}", e)]),
+            None => (Vec::new(), Vec::new()),
+        };
+        entries.extend(self.journal_pending.iter().cloned());
+        entries.iter()
+            .rev()
+            .filter(|e| e.op.touches_node(node_id))
+            .map(|e| format!("{}  {}", e.at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"), e.op.summary()))
+            .chain(errors)
+            .collect()
+    }
+
+    fn undo(&mut self) {
+        match self.history.undo(&mut self.graph) {
+            Some(label) => {
+                self.status_message = format!("Undid {}", label);
+                self.after_history_change();
+            }
+            None => self.status_message = "Nothing to undo".to_string(),
+        }
+    }
+
+    fn redo(&mut self) {
+        match self.history.redo(&mut self.graph) {
+            Some(label) => {
+                self.status_message = format!("Redid {}", label);
+                self.after_history_change();
+            }
+            None => self.status_message = "Nothing to redo".to_string(),
+        }
+    }
+
+    // 되돌린 그래프에 없는 노드/케이크의 선택과 편집 상태 정리
+    fn after_history_change(&mut self) {
+        if self.selected_node.as_ref().is_some_and(|id| self.graph.get_node(id).is_none()) {
+            self.selected_node = None;
+        }
+        if self.editing_node.as_ref().is_some_and(|id| self.graph.get_node(id).is_none()) {
+            self.editing_node = None;
+        }
+        if self.selected_cake.as_ref().is_some_and(|id| self.graph.get_cake(id).is_none()) {
+            self.selected_cake = None;
+        }
+        self.interaction_mode = InteractionMode::None;
+        self.drag_start_pos = None;
+        self.has_unsaved_changes = true;
+    }
+
+    fn matches_tag_filter(&self, node_id: &str) -> bool {
+        self.tag_filter.as_ref()
+            .is_none_or(|tag| self.graph.node_has_tag(node_id, tag))
+    }
+
+    // 편집 창의 제목/속성/태그가 노드와 다른지
+    fn edited_node_changed(&self, node_id: &str) -> bool {
+        let Some(node) = self.graph.get_node(node_id) else {
+            return false;
+        };
+        let tags: BTreeSet<String> = self.edit_tags.split(',').filter_map(normalize_tag).collect();
+        node.title != self.edit_title || node.properties != self.edit_properties || node.tags != tags
+    }
+
+    // 편집 창의 속성들을 노드에 반영
+    fn save_edited_properties(&mut self, node_id: &str) {
+        let stale: Vec<String> = self.graph.get_node(node_id)
+            .map(|n| n.properties.keys()
+                .filter(|key| !self.edit_properties.contains_key(*key))
+                .cloned()
+                .collect())
+            .unwrap_or_default();
+        for key in stale {
+            let _ = self.graph.remove_node_property(node_id, &key);
+        }
+
+        for (key, value) in std::mem::take(&mut self.edit_properties) {
+            if self.graph.node_property(node_id, &key) == Some(&value) {
+                continue;
+            }
+            if let Err(e) = self.graph.set_node_property(node_id, key.clone(), value) {
+                self.status_message = format!("Failed to set property '{}': {}", key, e);
+            }
+        }
+    }
+
+    fn sanitize_workspace_id(name: &str) -> String {
+        name.trim()
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect()
+    }
+
     fn create_demo_structures(&mut self) {
         // 첫 번째 케이크 (메인 프로젝트) - 1레이어로 시작
-        let cake1 = CakeStructure::new(
-            "main_project".to_string(),
+        let main_project = self.graph.create_cake(
             "Main Project".to_string(),
-            egui::Pos2::new(300.0, 300.0)
+            Position2D::new(300.0, 300.0)
         );
-        self.cakes.push(cake1);
         
         // 두 번째 케이크 (연구 영역)
-        let mut cake2 = CakeStructure::new(
-            "research".to_string(), 
-            "Research Area".to_string(),
-            egui::Pos2::new(700.0, 200.0)
+        let research = self.graph.add_cake(
+            Cake::new("Research Area".to_string(), Position2D::new(700.0, 200.0))
+                .with_theme(1) // 따뜻한 테마
         );
-        cake2.color_theme = 1; // 따뜻한 테마
-        self.cakes.push(cake2);
 
         // 기본 루트 노드들만 생성 (1레이어)
-        self.add_node_to_cake("Main Goal", NodeType::Root, "main_project", 0, 0.0);
-        self.add_node_to_cake("Research", NodeType::Root, "research", 0, 0.0);
+        self.add_node_to_cake("Main Goal", NodeType::Root, &main_project, 0, 0.0);
+        self.add_node_to_cake("Research", NodeType::Root, &research, 0, 0.0);
     }
 
     fn add_node_to_cake(&mut self, title: &str, node_type: NodeType, cake_id: &str, layer: usize, angle: f32) {
-        let radius = 80.0 * (0.8_f32.powi(layer as i32)).max(0.3);
-        let node = CakeNode {
-            id: format!("{}_{}", title.replace(" ", "_"), self.nodes.len()),
-            title: title.to_string(),
-            layer,
-            angle,
-            radius,
-            node_type,
-            cake_id: cake_id.to_string(),
-            selected: false,
-        };
-        
-        // 케이크의 최대 레이어 업데이트 (강제로 확장)
-        if let Some(cake) = self.cakes.iter_mut().find(|c| c.id == cake_id) {
-            cake.expand_to_layer(layer);
-            println!("Expanded cake '{}' to layer {}, max_layer now: {}", cake.title, layer, cake.max_layer);
+        let radius = Cake::node_radius(layer);
+        // 케이크의 최대 레이어도 그래프가 함께 확장
+        let result = self.history.apply(&mut self.graph, "Add node", |graph| {
+            graph.create_cake_node(title.to_string(), node_type, cake_id, layer, radius, angle)
+        });
+        if let Err(e) = result {
+            self.status_message = format!("Failed to add '{}': {}", title, e);
+            return;
         }
         
-        self.nodes.push(node);
-        println!("Added node '{}' to cake '{}' at layer {}", title, cake_id, layer);
-    }
-
-    fn add_connection(&mut self, from_title: &str, to_title: &str, connection_type: ConnectionType) {
-        if let (Some(from_node), Some(to_node)) = (
-            self.nodes.iter().find(|n| n.title == from_title),
-            self.nodes.iter().find(|n| n.title == to_title)
-        ) {
-            self.connections.push(Connection {
-                from_id: from_node.id.clone(),
-                to_id: to_node.id.clone(),
-                connection_type,
-            });
-        }
+        self.has_unsaved_changes = true;
     }
 
-    fn get_node_screen_pos(&self, node: &CakeNode, canvas_rect: egui::Rect) -> egui::Pos2 {
-        if let Some(cake) = self.cakes.iter().find(|c| c.id == node.cake_id) {
+    fn get_node_screen_pos(&self, node: &Node, canvas_rect: egui::Rect) -> egui::Pos2 {
+        if let Some(cake) = self.graph.get_node_cake(&node.id) {
             let layer_height = 60.0 * cake.size_scale;
             let y_offset = -(node.layer as f32 * layer_height);
             
-            let angle = node.angle;
-            let radius = node.radius * cake.size_scale;
+            let angle = node.layer_angle;
+            let radius = node.layer_radius * cake.size_scale;
             let x = cake.center.x + radius * angle.cos();
             let y = cake.center.y + y_offset + radius * angle.sin() * 0.3;
             
@@ -320,8 +873,8 @@ impl Cosmos3DApp {
         }
     }
 
-    fn find_node_at_pos(&self, pos: egui::Pos2, canvas_rect: egui::Rect) -> Option<&CakeNode> {
-        for node in &self.nodes {
+    fn find_node_at_pos(&self, pos: egui::Pos2, canvas_rect: egui::Rect) -> Option<&Node> {
+        for node in self.graph.get_nodes() {
             let node_pos = self.get_node_screen_pos(node, canvas_rect);
             let node_size = (15.0 + node.layer as f32 * 3.0) * self.viewport.zoom;
             
@@ -332,11 +885,11 @@ impl Cosmos3DApp {
         None
     }
 
-    fn find_cake_at_pos(&self, pos: egui::Pos2, canvas_rect: egui::Rect) -> Option<&CakeStructure> {
+    fn find_cake_at_pos(&self, pos: egui::Pos2, canvas_rect: egui::Rect) -> Option<&Cake> {
         let world_pos = self.viewport.screen_to_world(pos, canvas_rect);
         
-        for cake in &self.cakes {
-            let distance = world_pos.distance(cake.center);
+        for cake in self.graph.get_cakes() {
+            let distance = world_pos.distance(cake_center(cake));
             let cake_radius = 120.0 * cake.size_scale;
             
             if distance <= cake_radius {
@@ -348,27 +901,117 @@ impl Cosmos3DApp {
 
     fn create_new_cake(&mut self, pos: egui::Pos2, canvas_rect: egui::Rect) {
         let world_pos = self.viewport.screen_to_world(pos, canvas_rect);
-        let cake_id = format!("cake_{}", self.cakes.len());
-        let mut new_cake = CakeStructure::new(
-            cake_id.clone(),
-            self.new_cake_title.clone(),
-            world_pos
+        self.history.begin_group(&mut self.graph, "New cake");
+        let cake_id = self.graph.add_cake(
+            Cake::new(self.new_cake_title.clone(), Position2D::new(world_pos.x, world_pos.y))
+                .with_theme(self.new_cake_theme)
         );
-        new_cake.color_theme = self.new_cake_theme;
-        
-        self.cakes.push(new_cake);
         
         // 새 케이크에 기본 루트 노드 추가 (1레이어)
         self.add_node_to_cake("Root", NodeType::Root, &cake_id, 0, 0.0);
+        self.history.end_group(&mut self.graph);
     }
 }
 
 impl eframe::App for Cosmos3DApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // 단축키: Ctrl+S 저장
+        if ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::S)) {
+            self.save_current();
+        }
+
+        // 단축키: Ctrl+Z 실행 취소, Ctrl+Shift+Z 다시 실행 (글자 입력 중에는 입력창이 처리)
+        if !ctx.wants_keyboard_input() {
+            let (undo, redo) = ctx.input(|i| {
+                let z = i.modifiers.command && i.key_pressed(egui::Key::Z);
+                (z && !i.modifiers.shift, z && i.modifiers.shift)
+            });
+            if undo {
+                self.undo();
+            } else if redo {
+                self.redo();
+            }
+        }
+
+        // 이번 프레임의 변경을 저널 항목으로 (드래그처럼 묶는 중인 편집은 끝난 뒤에 한 번에)
+        if !self.history.in_group() {
+            let entries = self.journal.collect(&mut self.graph);
+            Journal::append(&mut self.journal_pending, entries);
+        }
+
+        // 허용은 됐지만 알려야 할 것들 (Warn 모드의 규칙 위반, 건너뛴 파일 등)은 상태 표시줄로
+        let warnings: Vec<String> = self.graph.take_warnings().iter().map(ToString::to_string)
+            .chain(self.storage_warnings.drain(..).map(|e| e.to_string()))
+            .collect();
+        if let Some(last) = warnings.last() {
+            self.status_message = match warnings.len() {
+                1 => format!("Warning: {}", last),
+                n => format!("Warning: {} (+{} more)", last, n - 1),
+            };
+        }
+
+        // 자동 저장 (변경 사항이 있을 때 주기적으로, 그리고 창을 닫을 때)
+        let now = ctx.input(|i| i.time);
+        let closing = ctx.input(|i| i.viewport().close_requested());
+        if self.has_unsaved_changes {
+            if closing || now - self.last_autosave >= AUTOSAVE_INTERVAL_SECS {
+                self.autosave(now, closing);
+            } else {
+                ctx.request_repaint_after(std::time::Duration::from_secs_f64(AUTOSAVE_INTERVAL_SECS));
+            }
+        }
+
         // 메인 다차원 케이크 뷰
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("🎂 Multi-Dimensional Cake Graph");
             
+            // 파일 메뉴
+            ui.horizontal(|ui| {
+                if ui.button("📂 Open").clicked() {
+                    self.show_open_dialog = true;
+                    self.open_listing = None;
+                }
+                if ui.button("💾 Save").clicked() {
+                    self.save_current();
+                }
+                if ui.button("💾 Save As").clicked() {
+                    self.save_as_name = self.current_workspace.clone().unwrap_or_default();
+                    self.show_save_as_dialog = true;
+                }
+                if ui.button("🗄 Backups").clicked() {
+                    self.show_backups = true;
+                    self.backup_listing = None;
+                    self.universe_backup_listing = None;
+                }
+
+                ui.separator();
+
+                let undo_hint = self.history.undo_label().map(|l| format!("Undo {} (Ctrl+Z)", l));
+                if ui.add_enabled(self.history.can_undo(), egui::Button::new("↶ Undo"))
+                    .on_hover_text(undo_hint.unwrap_or_default())
+                    .clicked()
+                {
+                    self.undo();
+                }
+                let redo_hint = self.history.redo_label().map(|l| format!("Redo {} (Ctrl+Shift+Z)", l));
+                if ui.add_enabled(self.history.can_redo(), egui::Button::new("↷ Redo"))
+                    .on_hover_text(redo_hint.unwrap_or_default())
+                    .clicked()
+                {
+                    self.redo();
+                }
+                
+                ui.separator();
+                
+                let name = self.current_workspace.as_deref().unwrap_or("Untitled");
+                let marker = if self.has_unsaved_changes { " *" } else { "" };
+                ui.label(format!("Workspace: {}{}", name, marker));
+                if !self.status_message.is_empty() {
+                    ui.separator();
+                    ui.label(&self.status_message);
+                }
+            });
+            
             // 컨트롤 패널
             ui.horizontal(|ui| {
                 ui.checkbox(&mut self.show_layers, "Show Layers");
@@ -379,19 +1022,17 @@ impl eframe::App for Cosmos3DApp {
                 
                 if ui.button("🎂 New Cake").clicked() {
                     // 빠른 생성: 기본 케이크 즉시 생성
-                    let cake_id = format!("cake_{}", self.cakes.len());
-                    let center = egui::Pos2::new(300.0 + self.cakes.len() as f32 * 300.0, 300.0);
-                    let mut new_cake = CakeStructure::new(
-                        cake_id.clone(),
-                        format!("Cake {}", self.cakes.len() + 1),
-                        center
+                    let cake_count = self.graph.cake_count();
+                    let center = Position2D::new(300.0 + cake_count as f32 * 300.0, 300.0);
+                    self.history.begin_group(&mut self.graph, "New cake");
+                    let cake_id = self.graph.add_cake(
+                        Cake::new(format!("Cake {}", cake_count + 1), center)
+                            .with_theme(cake_count % 4)
                     );
-                    new_cake.color_theme = self.cakes.len() % 4;
-                    self.cakes.push(new_cake);
                     
                     // 기본 루트 노드 추가
                     self.add_node_to_cake("Root", NodeType::Root, &cake_id, 0, 0.0);
-                    println!("Quick-created new cake: '{}'", cake_id);
+                    self.history.end_group(&mut self.graph);
                 }
                 
                 if ui.button("🎂 Custom Cake").clicked() {
@@ -417,6 +1058,38 @@ impl eframe::App for Cosmos3DApp {
                 
                 ui.separator();
                 
+                // 연결 타입 선택
+                ui.label("Link:");
+                egui::ComboBox::from_id_source("link_type")
+                    .selected_text(self.graph.relation_types().display_name(&self.new_relation_type))
+                    .show_ui(ui, |ui| {
+                        for relation_type in selectable_relation_types(&self.graph) {
+                            let name = self.graph.relation_types().display_name(&relation_type).to_string();
+                            ui.selectable_value(&mut self.new_relation_type, relation_type, name);
+                        }
+                    });
+                if ui.button("📋 Task Order").clicked() {
+                    self.show_task_order = !self.show_task_order;
+                }
+
+                ui.label("Tag:");
+                egui::ComboBox::from_id_source("tag_filter")
+                    .selected_text(self.tag_filter.as_deref().unwrap_or("All"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.tag_filter, None, "All");
+                        for (tag, count) in self.graph.all_tags() {
+                            ui.selectable_value(&mut self.tag_filter, Some(tag.to_string()), format!("{} ({})", tag, count));
+                        }
+                    });
+                if ui.button("🏷 Tags").clicked() {
+                    self.show_tag_manager = !self.show_tag_manager;
+                }
+                if ui.button("🧩 Types").clicked() {
+                    self.show_type_editor = !self.show_type_editor;
+                }
+                
+                ui.separator();
+                
                 // 뷰포트 컨트롤
                 ui.label(format!("Zoom: {:.1}x", self.viewport.zoom));
                 if ui.button("Reset View").clicked() {
@@ -426,11 +1099,11 @@ impl eframe::App for Cosmos3DApp {
                 ui.separator();
                 
                 // 케이크 상태 정보
-                ui.label(format!("Cakes: {}", self.cakes.len()));
-                ui.label(format!("Nodes: {}", self.nodes.len()));
+                ui.label(format!("Cakes: {}", self.graph.cake_count()));
+                ui.label(format!("Nodes: {}", self.graph.node_count()));
                 if let Some(selected_cake_id) = &self.selected_cake {
-                    if let Some(cake) = self.cakes.iter().find(|c| c.id == *selected_cake_id) {
-                        ui.label(format!("Selected: '{}' (Layers: {})", cake.title, cake.max_layer + 1));
+                    if let Some(cake) = self.graph.get_cake(selected_cake_id) {
+                        ui.label(format!("Selected: '{}' (Layers: {})", cake.title, cake.layer_count()));
                     }
                 }
             });
@@ -459,20 +1132,20 @@ impl eframe::App for Cosmos3DApp {
                 let click_pos = response.interact_pointer_pos().unwrap_or_default();
                 
                 // 노드 클릭 우선 확인
-                let clicked_node_id = if let Some(clicked_node) = self.find_node_at_pos(click_pos, canvas_rect) {
-                    Some(clicked_node.id.clone())
-                } else {
-                    None
-                };
+                let clicked_node_id = self.find_node_at_pos(click_pos, canvas_rect)
+                    .map(|clicked_node| clicked_node.id.clone());
                 
                 if let Some(node_id) = clicked_node_id {
                     self.selected_node = Some(node_id.clone());
                     
                     // 더블클릭으로 편집 모드
                     if response.double_clicked() {
-                        if let Some(node) = self.nodes.iter().find(|n| n.id == node_id) {
-                            self.editing_node = Some(node_id);
+                        if let Some(node) = self.graph.get_node(&node_id) {
                             self.edit_title = node.title.clone();
+                            self.edit_properties = node.properties.clone();
+                            self.edit_tags = node.tags.iter().cloned().collect::<Vec<_>>().join(", ");
+                            self.editing_node = Some(node_id);
+                            self.node_journal = None;
                         }
                     }
                 } else {
@@ -506,6 +1179,8 @@ impl eframe::App for Cosmos3DApp {
                     // 케이크에서 드래그 시작 = 케이크 이동
                     else if let Some(cake) = self.find_cake_at_pos(drag_pos, canvas_rect) {
                         self.interaction_mode = InteractionMode::DraggingCake(cake.id.clone());
+                        // 드래그 전체를 한 번의 이동으로 기록
+                        self.history.begin_group(&mut self.graph, "Move cake");
                     }
                     // 빈 공간에서 드래그 = 팬 모드 (두 손가락 또는 일반 드래그)
                     else {
@@ -528,10 +1203,14 @@ impl eframe::App for Cosmos3DApp {
                         // 연결 생성 중 - 선 그리기는 렌더링에서 처리
                     }
                     InteractionMode::DraggingCake(cake_id) => {
-                        // 케이크 전체 이동
-                        if let Some(cake) = self.cakes.iter_mut().find(|c| c.id == *cake_id) {
-                            let world_delta = drag_delta / self.viewport.zoom;
-                            cake.center += world_delta;
+                        // 케이크 전체 이동 (움직이지 않은 프레임은 고치지 않음)
+                        if drag_delta != egui::Vec2::ZERO {
+                            if let Some(mut cake) = self.graph.get_cake_mut(cake_id) {
+                                let world_delta = drag_delta / self.viewport.zoom;
+                                cake.center.x += world_delta.x;
+                                cake.center.y += world_delta.y;
+                                self.has_unsaved_changes = true;
+                            }
                         }
                         self.drag_start_pos = Some(drag_pos);
                     }
@@ -548,39 +1227,27 @@ impl eframe::App for Cosmos3DApp {
             if response.drag_released() {
                 if let InteractionMode::CreatingConnection(from_id) = &self.interaction_mode {
                     if let Some(pos) = response.interact_pointer_pos() {
-                        if let Some(target_node) = self.find_node_at_pos(pos, canvas_rect) {
-                            // 연결 타입 결정 (같은 케이크인지 다른 케이크인지)
-                            let from_cake = self.nodes.iter().find(|n| n.id == *from_id).map(|n| &n.cake_id);
-                            let to_cake = Some(&target_node.cake_id);
-                            
-                            let connection_type = if from_cake == to_cake {
-                                ConnectionType::IntraCake
-                            } else {
-                                ConnectionType::InterCake
-                            };
-                            
+                        if let Some(target_id) = self.find_node_at_pos(pos, canvas_rect).map(|n| n.id.clone()) {
                             // 중복 연결 방지
-                            let connection_exists = self.connections.iter().any(|c| {
-                                (c.from_id == *from_id && c.to_id == target_node.id) ||
-                                (c.from_id == target_node.id && c.to_id == *from_id)
-                            });
-                            
-                            if !connection_exists && *from_id != target_node.id {
-                                self.connections.push(Connection {
-                                    from_id: from_id.clone(),
-                                    to_id: target_node.id.clone(),
-                                    connection_type,
-                                });
+                            if !self.graph.are_connected(from_id, &target_id) && *from_id != target_id {
+                                let relation_type = self.new_relation_type.clone();
+                                match self.history.apply(&mut self.graph, "Connect", |graph| graph.add_relation(from_id, &target_id, relation_type)) {
+                                    Ok(_) => self.has_unsaved_changes = true,
+                                    Err(e) => self.status_message = format!("Cannot connect: {}", e),
+                                }
                             }
                         }
                     }
                 }
+                if matches!(self.interaction_mode, InteractionMode::DraggingCake(_)) {
+                    self.history.end_group(&mut self.graph);
+                }
                 self.interaction_mode = InteractionMode::None;
                 self.drag_start_pos = None;
             }
 
             // 케이크들 그리기 (동적 레이어)
-            for cake in &self.cakes {
+            for cake in self.graph.get_cakes() {
                 let is_selected = self.selected_cake.as_ref() == Some(&cake.id);
                 
                 // 케이크 층들 그리기 (동적으로 확장된 레이어까지)
@@ -588,14 +1255,14 @@ impl eframe::App for Cosmos3DApp {
                     for layer in 0..=cake.max_layer {
                         let layer_radius = ((80.0 - layer as f32 * 15.0) * cake.size_scale).max(20.0);
                         let y_offset = -(layer as f32 * 60.0 * cake.size_scale);
-                        let world_center = egui::Pos2::new(cake.center.x, cake.center.y + y_offset);
+                        let world_center = cake_center(cake) + egui::Vec2::new(0.0, y_offset);
                         let screen_center = self.viewport.world_to_screen(world_center, canvas_rect);
                         let screen_radius = layer_radius * self.viewport.zoom;
                         
                         painter.circle_filled(
                             screen_center,
                             screen_radius,
-                            cake.get_layer_color(layer),
+                            cake_layer_color(cake, layer),
                         );
                         
                         if is_selected {
@@ -610,7 +1277,7 @@ impl eframe::App for Cosmos3DApp {
                 
                 // 케이크 제목
                 if self.show_cake_titles {
-                    let title_world_pos = cake.center + egui::Vec2::new(0.0, 100.0 * cake.size_scale);
+                    let title_world_pos = cake_center(cake) + egui::Vec2::new(0.0, 100.0 * cake.size_scale);
                     let title_screen_pos = self.viewport.world_to_screen(title_world_pos, canvas_rect);
                     
                     painter.text(
@@ -625,27 +1292,35 @@ impl eframe::App for Cosmos3DApp {
 
             // 연결선 그리기
             if self.show_connections {
-                for connection in &self.connections {
+                for relation in self.graph.get_relations() {
                     if let (Some(from_node), Some(to_node)) = (
-                        self.nodes.iter().find(|n| n.id == connection.from_id),
-                        self.nodes.iter().find(|n| n.id == connection.to_id)
+                        self.graph.get_node(&relation.source_id),
+                        self.graph.get_node(&relation.target_id)
                     ) {
                         let from_pos = self.get_node_screen_pos(from_node, canvas_rect);
                         let to_pos = self.get_node_screen_pos(to_node, canvas_rect);
                         
-                        let (color, width) = match connection.connection_type {
-                            ConnectionType::IntraCake => (egui::Color32::from_rgba_unmultiplied(255, 255, 255, 150), 2.0),
-                            ConnectionType::InterCake => (egui::Color32::from_rgba_unmultiplied(255, 100, 100, 200), 3.0),
+                        // 같은 케이크 안의 연결인지, 케이크 간 연결인지
+                        let (mut color, mut width) = match self.graph.relation_scope(relation) {
+                            CakeScope::IntraCake => (egui::Color32::from_rgba_unmultiplied(255, 255, 255, 150), 2.0),
+                            CakeScope::InterCake => (egui::Color32::from_rgba_unmultiplied(255, 100, 100, 200), 3.0),
                         };
+                        // 직접 정의한 관계 타입은 정의된 색과 두께로
+                        if !relation.relation_type.is_builtin() {
+                            let [r, g, b, a] = self.graph.relation_types().color(&relation.relation_type);
+                            color = egui::Color32::from_rgba_unmultiplied(r, g, b, a);
+                            width = self.graph.relation_types().thickness(&relation.relation_type);
+                        }
                         
-                        painter.line_segment([from_pos, to_pos], egui::Stroke::new(width * self.viewport.zoom, color));
+                        let dim = if self.matches_tag_filter(&from_node.id) && self.matches_tag_filter(&to_node.id) { 1.0 } else { 0.2 };
+                        painter.line_segment([from_pos, to_pos], egui::Stroke::new(width * self.viewport.zoom, color.gamma_multiply(dim)));
                     }
                 }
             }
 
             // 연결 생성 중인 경우 임시 선 그리기
             if let InteractionMode::CreatingConnection(from_id) = &self.interaction_mode {
-                if let Some(from_node) = self.nodes.iter().find(|n| n.id == *from_id) {
+                if let Some(from_node) = self.graph.get_node(from_id) {
                     if let Some(mouse_pos) = response.interact_pointer_pos() {
                         let from_pos = self.get_node_screen_pos(from_node, canvas_rect);
                         painter.line_segment(
@@ -657,7 +1332,7 @@ impl eframe::App for Cosmos3DApp {
             }
 
             // 노드들 그리기
-            for node in &self.nodes {
+            for node in self.graph.get_nodes() {
                 let node_pos = self.get_node_screen_pos(node, canvas_rect);
                 let node_size = (15.0 + node.layer as f32 * 3.0) * self.viewport.zoom;
                 
@@ -669,14 +1344,16 @@ impl eframe::App for Cosmos3DApp {
                 };
                 let border_width = if is_selected { 3.0 } else { 2.0 };
                 
-                painter.circle_filled(node_pos, node_size, node.node_type.color());
-                painter.circle_stroke(node_pos, node_size, egui::Stroke::new(border_width * self.viewport.zoom, border_color));
+                // 태그 필터에 걸리지 않는 노드는 흐리게
+                let dim = if self.matches_tag_filter(&node.id) { 1.0 } else { 0.2 };
+                painter.circle_filled(node_pos, node_size, node_type_color(&self.graph, &node.node_type).gamma_multiply(dim));
+                painter.circle_stroke(node_pos, node_size, egui::Stroke::new(border_width * self.viewport.zoom, border_color.gamma_multiply(dim)));
                 
                 // 노드 아이콘
                 painter.text(
                     node_pos,
                     egui::Align2::CENTER_CENTER,
-                    node.node_type.emoji(),
+                    self.graph.node_types().emoji(&node.node_type),
                     egui::FontId::proportional(12.0 * self.viewport.zoom),
                     egui::Color32::WHITE,
                 );
@@ -694,6 +1371,176 @@ impl eframe::App for Cosmos3DApp {
             }
         });
 
+        // 워크스페이스 열기 창
+        if self.show_open_dialog {
+            let mut open_id = None;
+            let mut open_universe_id = None;
+            let mut refresh = false;
+            let storage = &self.storage;
+            let storage_warnings = &mut self.storage_warnings;
+            let (workspaces, universes) = self.open_listing.get_or_insert_with(|| match storage {
+                Some(storage) => (
+                    storage.list_workspaces(),
+                    storage.list_universes().map(|(universes, warnings)| {
+                        storage_warnings.extend(warnings);
+                        universes
+                    }),
+                ),
+                None => (Err(StorageError::Unavailable), Ok(Vec::new())),
+            });
+            egui::Window::new("📂 Open Workspace")
+                .show(ctx, |ui| {
+                    let workspaces = match workspaces {
+                        Ok(workspaces) => workspaces.as_slice(),
+                        Err(e) => {
+                            ui.colored_label(egui::Color32::LIGHT_RED, format!("Failed to list workspaces: {}", e));
+                            &[]
+                        }
+                    };
+                    if workspaces.is_empty() {
+                        ui.label("No saved workspaces");
+                    }
+                    for info in workspaces {
+                        ui.horizontal(|ui| {
+                            if ui.button(&info.id).clicked() {
+                                open_id = Some(info.id.clone());
+                            }
+                            ui.label(info.modified_at.format("%Y-%m-%d %H:%M").to_string());
+                        });
+                    }
+
+                    // 예전 버전에서 저장한 유니버스 파일
+                    match universes {
+                        Ok(universes) if universes.is_empty() => {}
+                        Ok(universes) => {
+                            ui.separator();
+                            ui.label("Universes");
+                            for info in universes.iter() {
+                                ui.horizontal(|ui| {
+                                    Self::draw_thumbnail(ui, &info.thumbnail);
+                                    if ui.button(&info.title).clicked() {
+                                        open_universe_id = Some(info.id.clone());
+                                    }
+                                    ui.label(format!("{} nodes, {} relations", info.node_count, info.relation_count));
+                                    ui.label(info.modified_at.format("%Y-%m-%d %H:%M").to_string());
+                                }).response.on_hover_text(if info.tags.is_empty() {
+                                    "No tags".to_string()
+                                } else {
+                                    info.tags.iter().cloned().collect::<Vec<_>>().join(", ")
+                                });
+                            }
+                        }
+                        Err(e) => {
+                            ui.colored_label(egui::Color32::LIGHT_RED, format!("Failed to list universes: {}", e));
+                        }
+                    }
+                    
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button("🔄 Refresh").clicked() {
+                            refresh = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.show_open_dialog = false;
+                        }
+                    });
+                });
+            
+            if refresh {
+                self.open_listing = None;
+            }
+            if let Some(id) = open_id {
+                self.open_workspace(&id);
+                self.show_open_dialog = false;
+            }
+            if let Some(id) = open_universe_id {
+                self.open_universe(&id);
+                self.show_open_dialog = false;
+            }
+        }
+
+        // 다른 이름으로 저장 창
+        if self.show_save_as_dialog {
+            egui::Window::new("💾 Save Workspace As")
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut self.save_as_name);
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        if ui.button("Save").clicked() {
+                            let id = Self::sanitize_workspace_id(&self.save_as_name);
+                            if !id.is_empty() {
+                                self.save_workspace_as(&id);
+                                self.show_save_as_dialog = false;
+                            }
+                        }
+                        
+                        if ui.button("Cancel").clicked() {
+                            self.show_save_as_dialog = false;
+                        }
+                    });
+                });
+        }
+
+        // 백업 목록 창
+        if self.show_backups {
+            let id = self.current_workspace.clone().unwrap_or_else(|| AUTOSAVE_ID.to_string());
+            let universe_id = self.opened_universe.clone();
+            let mut restore = None;
+            let mut restore_universe = None;
+            let mut refresh = false;
+            // 다른 워크스페이스/유니버스를 열었으면 그 백업들로 다시 읽는다
+            if self.backup_listing.as_ref().is_none_or(|(listed_id, _)| *listed_id != id) {
+                self.backup_listing = Some((id.clone(), self.storage().and_then(|storage| storage.list_workspace_backups(&id))));
+            }
+            if let Some(universe_id) = &universe_id {
+                if self.universe_backup_listing.as_ref().is_none_or(|(listed_id, _)| listed_id != universe_id) {
+                    let backups = self.storage().and_then(|storage| storage.list_universe_backups(universe_id));
+                    self.universe_backup_listing = Some((universe_id.clone(), backups));
+                }
+            }
+            let backups = self.backup_listing.as_ref().map(|(_, backups)| backups);
+            let universe_backups = self.universe_backup_listing.as_ref().map(|(_, backups)| backups);
+            egui::Window::new("🗄 Backups")
+                .show(ctx, |ui| {
+                    ui.label(format!("Workspace '{}'", id));
+                    restore = Self::draw_backup_list(ui, backups, "No backups yet (one is kept each time the workspace file is overwritten)");
+
+                    // 예전 유니버스 파일에서 불러왔으면 그 파일의 백업도
+                    if let Some(universe_id) = &universe_id {
+                        ui.separator();
+                        ui.label(format!("Universe '{}'", universe_id));
+                        restore_universe = Self::draw_backup_list(ui, universe_backups, "No backups yet (one is kept each time the universe file is overwritten)");
+                    }
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button("🔄 Refresh").clicked() {
+                            refresh = true;
+                        }
+                        if ui.button("Close").clicked() {
+                            self.show_backups = false;
+                        }
+                    });
+                });
+
+            // 복원하면 현재 파일이 새 백업으로 남으므로 목록도 다시 읽는다
+            if let Some(name) = restore {
+                self.restore_backup(&id, &name);
+                refresh = true;
+            }
+            if let (Some(universe_id), Some(name)) = (&universe_id, restore_universe) {
+                self.restore_universe_backup(universe_id, &name);
+                refresh = true;
+            }
+            if refresh {
+                self.backup_listing = None;
+                self.universe_backup_listing = None;
+            }
+        }
+
         // 케이크 생성 창
         if self.show_cake_creator {
             egui::Window::new("🎂 Create New Cake")
@@ -723,7 +1570,7 @@ impl eframe::App for Cosmos3DApp {
                     ui.horizontal(|ui| {
                         if ui.button("Create").clicked() {
                             if !self.new_cake_title.is_empty() {
-                                let center = egui::Pos2::new(400.0 + self.cakes.len() as f32 * 200.0, 300.0);
+                                let center = egui::Pos2::new(400.0 + self.graph.cake_count() as f32 * 200.0, 300.0);
                                 let canvas_rect = egui::Rect::from_center_size(egui::Pos2::new(400.0, 300.0), egui::Vec2::new(800.0, 600.0));
                                 self.create_new_cake(center, canvas_rect);
                                 self.new_cake_title.clear();
@@ -751,29 +1598,28 @@ impl eframe::App for Cosmos3DApp {
                     ui.horizontal(|ui| {
                         ui.label("Type:");
                         egui::ComboBox::from_label("")
-                            .selected_text(format!("{} {:?}", self.new_node_type.emoji(), self.new_node_type))
+                            .selected_text(format!("{} {}", self.graph.node_types().emoji(&self.new_node_type), self.new_node_type.name()))
                             .show_ui(ui, |ui| {
-                                ui.selectable_value(&mut self.new_node_type, NodeType::Root, "🌌 Root");
-                                ui.selectable_value(&mut self.new_node_type, NodeType::Concept, "💭 Concept");
-                                ui.selectable_value(&mut self.new_node_type, NodeType::Task, "📋 Task");
-                                ui.selectable_value(&mut self.new_node_type, NodeType::Note, "📝 Note");
+                                for node_type in selectable_node_types(&self.graph) {
+                                    let label = format!("{} {}", self.graph.node_types().emoji(&node_type), node_type.name());
+                                    ui.selectable_value(&mut self.new_node_type, node_type, label);
+                                }
                             });
                     });
                     
                     // 케이크 선택
-                    if !self.cakes.is_empty() {
+                    if let Some(first_cake) = self.graph.get_cakes().next() {
                         ui.horizontal(|ui| {
                             ui.label("Cake:");
-                            let selected_cake_id = self.selected_cake.as_deref().unwrap_or(&self.cakes[0].id);
-                            let selected_cake_title = self.cakes.iter()
-                                .find(|c| c.id == selected_cake_id)
+                            let selected_cake_id = self.selected_cake.as_deref().unwrap_or(&first_cake.id);
+                            let selected_cake_title = self.graph.get_cake(selected_cake_id)
                                 .map(|c| c.title.as_str())
                                 .unwrap_or("Unknown");
                                 
                             egui::ComboBox::from_label("")
                                 .selected_text(selected_cake_title)
                                 .show_ui(ui, |ui| {
-                                    for cake in &self.cakes {
+                                    for cake in self.graph.get_cakes() {
                                         ui.selectable_value(&mut self.selected_cake, Some(cake.id.clone()), &cake.title);
                                     }
                                 });
@@ -785,22 +1631,19 @@ impl eframe::App for Cosmos3DApp {
                             if !self.new_node_title.is_empty() {
                                 let cake_id = if let Some(selected) = &self.selected_cake {
                                     selected.clone()
-                                } else if !self.cakes.is_empty() {
-                                    self.cakes[0].id.clone()
+                                } else if let Some(first_cake) = self.graph.get_cakes().next() {
+                                    first_cake.id.clone()
                                 } else {
-                                    println!("No cakes available for node creation");
+                                    self.status_message = "No cakes available for node creation".to_string();
                                     self.show_create_menu = false;
                                     return; // 케이크가 없으면 생성하지 않음
                                 };
                                 
-                                let layer = self.new_node_type.layer();
-                                let angle = self.nodes.iter()
-                                    .filter(|n| n.cake_id == cake_id && n.layer == layer)
-                                    .count() as f32 * 1.2;
+                                let layer = self.graph.node_types().default_layer(&self.new_node_type);
+                                let angle = self.graph.get_cake_layer_nodes(&cake_id, layer).len() as f32 * 1.2;
                                 
                                 let title = self.new_node_title.clone();
-                                let node_type = self.new_node_type;
-                                println!("Creating node '{}' of type {:?} at layer {} in cake '{}'", title, node_type, layer, cake_id);
+                                let node_type = self.new_node_type.clone();
                                 self.add_node_to_cake(&title, node_type, &cake_id, layer, angle);
                                 self.new_node_title.clear();
                             }
@@ -816,24 +1659,147 @@ impl eframe::App for Cosmos3DApp {
 
         // 노드 편집 창
         if let Some(editing_id) = &self.editing_node.clone() {
+            if self.node_journal.as_ref().is_none_or(|(id, _)| id != editing_id) {
+                self.node_journal = Some((editing_id.clone(), self.node_journal_lines(editing_id)));
+            }
             egui::Window::new("Edit Node")
                 .show(ctx, |ui| {
                     ui.horizontal(|ui| {
                         ui.label("Title:");
                         ui.text_edit_singleline(&mut self.edit_title);
                     });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Tags:");
+                        ui.add(egui::TextEdit::singleline(&mut self.edit_tags).hint_text("area/backend, urgent"));
+                    });
                     
+                    ui.separator();
+                    ui.label("Properties:");
+                    let mut removed_key = None;
+                    for (key, value) in self.edit_properties.iter_mut() {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{} ({})", key, value.type_name()));
+                            edit_property_value(ui, value, &self.graph, &format!("property_{}", key));
+                            if ui.small_button("✖").clicked() {
+                                removed_key = Some(key.clone());
+                            }
+                        });
+                    }
+                    if let Some(key) = removed_key {
+                        self.edit_properties.remove(&key);
+                    }
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut self.new_property_key).hint_text("key").desired_width(80.0));
+                        egui::ComboBox::from_id_source("new_property_type")
+                            .selected_text(PROPERTY_TYPES[self.new_property_type])
+                            .show_ui(ui, |ui| {
+                                for (i, type_name) in PROPERTY_TYPES.iter().enumerate() {
+                                    ui.selectable_value(&mut self.new_property_type, i, *type_name);
+                                }
+                            });
+                        let key = self.new_property_key.trim().to_string();
+                        if ui.button("Add").clicked() && !key.is_empty() {
+                            let value = empty_property(PROPERTY_TYPES[self.new_property_type], &self.graph);
+                            self.edit_properties.insert(key, value);
+                            self.new_property_key.clear();
+                        }
+                    });
+                    ui.separator();
+
+                    if let Ok(log) = self.graph.revision_log(editing_id) {
+                        if !log.is_empty() {
+                            ui.collapsing("History", |ui| {
+                                for (i, diff) in log.iter().enumerate() {
+                                    ui.label(format!("v{}", i + 2));
+                                    if diff.is_empty() {
+                                        ui.label("  (no changes)");
+                                    }
+                                    for change in &diff.changes {
+                                        ui.label(format!("  {}", change));
+                                    }
+                                }
+                            });
+                        }
+                    }
+
+                    if let Some((_, lines)) = &self.node_journal {
+                        if !lines.is_empty() {
+                            ui.collapsing("Journal", |ui| {
+                                for line in lines {
+                                    ui.label(line);
+                                }
+                            });
+                        }
+                    }
+
+                    let has_children = self.graph.get_node(editing_id)
+                        .is_some_and(|n| !n.children_ids.is_empty());
+                    if has_children {
+                        ui.horizontal(|ui| {
+                            ui.label("On delete:");
+                            egui::ComboBox::from_id_source("delete_policy")
+                                .selected_text(self.delete_policy.display_name())
+                                .show_ui(ui, |ui| {
+                                    for policy in [DeletePolicy::Orphan, DeletePolicy::PromoteToGrandparent, DeletePolicy::Cascade] {
+                                        ui.selectable_value(&mut self.delete_policy, policy, policy.display_name());
+                                    }
+                                });
+                        });
+                    }
+
                     ui.horizontal(|ui| {
                         if ui.button("Save").clicked() {
-                            if let Some(node) = self.nodes.iter_mut().find(|n| n.id == *editing_id) {
-                                node.title = self.edit_title.clone();
+                            // 바뀐 것이 없으면 편집 기록도 저널도 남기지 않음
+                            if self.edited_node_changed(editing_id) {
+                                self.history.begin_group(&mut self.graph, "Edit node");
+                                let title_changed = self.graph.get_node(editing_id)
+                                    .is_some_and(|n| n.title != self.edit_title);
+                                if title_changed {
+                                    if let Some(mut node) = self.graph.get_node_mut(editing_id) {
+                                        node.title = self.edit_title.clone();
+                                        node.updated_at = chrono::Utc::now();
+                                    }
+                                }
+                                self.save_edited_properties(editing_id);
+                                if let Err(e) = self.graph.set_tags(editing_id, self.edit_tags.split(',')) {
+                                    self.status_message = format!("Failed to set tags: {}", e);
+                                }
+                                self.history.end_group(&mut self.graph);
+                                self.has_unsaved_changes = true;
                             }
                             self.editing_node = None;
                         }
                         
+                        if ui.button("Evolve").clicked() {
+                            match self.history.apply(&mut self.graph, "Evolve node", |graph| graph.evolve_node_with_content(editing_id, None, None)) {
+                                Ok(new_id) => {
+                                    self.selected_node = Some(new_id);
+                                    self.has_unsaved_changes = true;
+                                }
+                                Err(e) => self.status_message = format!("Failed to evolve node: {}", e),
+                            }
+                            self.editing_node = None;
+                        }
+
+                        if ui.button("Duplicate").clicked() {
+                            let parent_id = self.graph.get_node(editing_id).and_then(|n| n.parent_id.clone());
+                            match self.history.apply(&mut self.graph, "Duplicate node", |graph| graph.duplicate_subtree(editing_id, parent_id.as_deref())) {
+                                Ok(copy_id) => {
+                                    self.selected_node = Some(copy_id);
+                                    self.has_unsaved_changes = true;
+                                }
+                                Err(e) => self.status_message = format!("Failed to duplicate node: {}", e),
+                            }
+                            self.editing_node = None;
+                        }
+
                         if ui.button("Delete").clicked() {
-                            self.nodes.retain(|n| n.id != *editing_id);
-                            self.connections.retain(|c| c.from_id != *editing_id && c.to_id != *editing_id);
+                            let policy = self.delete_policy;
+                            match self.history.apply(&mut self.graph, "Delete node", |graph| graph.remove_node_with_policy(editing_id, policy)) {
+                                Ok(_) => self.has_unsaved_changes = true,
+                                Err(e) => self.status_message = format!("Failed to delete node: {}", e),
+                            }
                             self.editing_node = None;
                             self.selected_node = None;
                         }
@@ -845,6 +1811,167 @@ impl eframe::App for Cosmos3DApp {
                 });
         }
 
+        // 태그 관리 창 (이름 바꾸기, 이미 있는 태그로 바꾸면 합쳐짐)
+        if self.show_tag_manager {
+            let mut open = true;
+            egui::Window::new("🏷 Tags")
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    let tags = self.graph.all_tags();
+                    if tags.is_empty() {
+                        ui.label("No tags yet");
+                    }
+                    for (tag, count) in &tags {
+                        ui.horizontal(|ui| {
+                            if ui.selectable_label(self.rename_tag_from == *tag, *tag).clicked() {
+                                self.rename_tag_from = tag.to_string();
+                                self.rename_tag_to = tag.to_string();
+                            }
+                            ui.label(format!("{} nodes", count));
+                        });
+                    }
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Rename '{}' to", self.rename_tag_from));
+                        ui.text_edit_singleline(&mut self.rename_tag_to);
+                    });
+                    if ui.button("Rename / Merge").clicked() && !self.rename_tag_from.is_empty() {
+                        let (from, to) = (&self.rename_tag_from, &self.rename_tag_to);
+                        let changed = if self.graph.nodes_with_tag(from).is_empty() {
+                            0
+                        } else {
+                            self.history.apply(&mut self.graph, "Rename tag", |graph| Ok(graph.rename_tag(from, to))).unwrap_or(0)
+                        };
+                        if changed > 0 {
+                            self.has_unsaved_changes = true;
+                            self.tag_filter = None;
+                        }
+                        self.status_message = format!("Retagged {} nodes", changed);
+                        self.rename_tag_from.clear();
+                    }
+                });
+            self.show_tag_manager = open;
+        }
+
+        // 타입 창 (유니버스 전용 노드/관계 타입 정의)
+        if self.show_type_editor {
+            let mut open = true;
+            egui::Window::new("🧩 Types")
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Schema:");
+                        ui.text_edit_singleline(&mut self.schema_path);
+                        if ui.button("Load").clicked() {
+                            self.load_schema();
+                        }
+                        if ui.button("Export").clicked() {
+                            self.export_schema();
+                        }
+                    });
+                    for (layer, def) in self.graph.layers().iter().enumerate() {
+                        let types = if def.node_types.is_empty() { "any".to_string() } else { def.node_types.join(", ") };
+                        ui.label(format!("Layer {} - {}: {}", layer + 1, def.name, types));
+                    }
+
+                    ui.separator();
+                    let types = selectable_node_types(&self.graph);
+                    for node_type in &types {
+                        if let Some(def) = self.graph.node_types().get(node_type) {
+                            ui.label(format!(
+                                "{} {} (layer {}) → {}",
+                                def.emoji,
+                                def.name,
+                                def.default_layer + 1,
+                                def.allowed_children.join(", ")
+                            ));
+                        }
+                    }
+
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut self.new_type_name);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Emoji:");
+                        ui.add(egui::TextEdit::singleline(&mut self.new_type_emoji).desired_width(40.0));
+                        ui.label("Layer:");
+                        ui.add(egui::DragValue::new(&mut self.new_type_layer).clamp_range(0..=9));
+                        ui.color_edit_button_srgba(&mut self.new_type_color);
+                    });
+                    ui.label("Children:");
+                    node_type_checkboxes(ui, &types, &mut self.new_type_children);
+                    ui.label("Allowed under:");
+                    node_type_checkboxes(ui, &types, &mut self.new_type_parents);
+
+                    if ui.button("Add Type").clicked() {
+                        self.add_node_type();
+                    }
+
+                    ui.separator();
+                    ui.heading("Relation Types");
+                    for relation_type in selectable_relation_types(&self.graph) {
+                        if let Some(def) = self.graph.relation_types().get(&relation_type) {
+                            let arrow = if def.directed { "→" } else { "↔" };
+                            let sources = if def.allowed_sources.is_empty() { "any".to_string() } else { def.allowed_sources.join(", ") };
+                            let targets = if def.allowed_targets.is_empty() { "any".to_string() } else { def.allowed_targets.join(", ") };
+                            ui.label(format!("{}: {} {} {}", def.display_name, sources, arrow, targets));
+                        }
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut self.new_relation_name);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_srgba(&mut self.new_relation_color);
+                        ui.label("Width:");
+                        ui.add(egui::DragValue::new(&mut self.new_relation_thickness).speed(0.1).clamp_range(0.5..=8.0));
+                        ui.checkbox(&mut self.new_relation_directed, "Directed");
+                    });
+                    ui.label("From (none = any):");
+                    node_type_checkboxes(ui, &types, &mut self.new_relation_sources);
+                    ui.label("To (none = any):");
+                    node_type_checkboxes(ui, &types, &mut self.new_relation_targets);
+
+                    if ui.button("Add Relation Type").clicked() {
+                        self.add_relation_type();
+                    }
+                });
+            self.show_type_editor = open;
+        }
+
+        // 작업 순서 창 (의존성을 먼저 해야 하는 순서)
+        if self.show_task_order {
+            let mut open = true;
+            egui::Window::new("📋 Task Order")
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    match self.graph.task_order() {
+                        Ok(tasks) if tasks.is_empty() => {
+                            ui.label("No tasks yet");
+                        }
+                        Ok(tasks) => {
+                            for (i, task) in tasks.iter().enumerate() {
+                                ui.label(format!("{}. {}", i + 1, task.title));
+                            }
+                        }
+                        Err(GraphError::DependencyCycle(cycle)) => {
+                            let titles: Vec<&str> = cycle.iter()
+                                .filter_map(|id| self.graph.get_node(id))
+                                .map(|n| n.title.as_str())
+                                .collect();
+                            ui.colored_label(egui::Color32::RED, format!("Dependency cycle: {}", titles.join(" → ")));
+                        }
+                        Err(e) => {
+                            ui.colored_label(egui::Color32::RED, e.to_string());
+                        }
+                    }
+                });
+            self.show_task_order = open;
+        }
+
         // 도움말 패널
         egui::Window::new("🎮 Enhanced Multi-Dimensional Controls")
             .default_pos(egui::Pos2::new(10.0, 10.0))
@@ -867,6 +1994,8 @@ impl eframe::App for Cosmos3DApp {
                 
                 ui.colored_label(egui::Color32::LIGHT_GREEN, "🔗 Connections:");
                 ui.label("• Drag node to node: Connect");
+                ui.label("• Link: Type of new connections");
+                ui.label("• Dependency A → B: B comes first");
                 ui.label("• White lines: Intra-cake");
                 ui.label("• Red lines: Inter-cake");
                 
@@ -877,6 +2006,12 @@ impl eframe::App for Cosmos3DApp {
                 ui.label("• Scroll wheel: Zoom in/out");
                 ui.label("• Drag empty space: Pan");
                 ui.label("• Reset View: Return to origin");
+                
+                ui.separator();
+                
+                ui.colored_label(egui::Color32::LIGHT_GRAY, "💾 Files:");
+                ui.label("• Ctrl+S: Save workspace");
+                ui.label("• Autosave: Every 30s and on exit");
             });
     }
 }
@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use super::{create_dir, read_dir, write_file, Storage, StorageError, StorageResult};
+
+// 백업 파일 이름에 들어가는 시각 (이름순 정렬이 시간순이 되도록)
+const BACKUP_TIME_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+/// 저장하기 전에 남겨둔 이전 파일 하나
+#[derive(Clone, Debug)]
+pub struct BackupInfo {
+    /// 워크스페이스/유니버스 ID
+    pub id: String,
+    /// 백업 파일 이름 (`<원래 파일 이름>.<시각>.json`, 같은 시각에 또 남기면 시각 뒤에 `-2`, `-3`, ...)
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    // 같은 시각의 백업끼리 순서 (이름의 `-N`, 없으면 1)
+    seq: u32,
+}
+
+impl BackupInfo {
+    // 백업 파일 이름에서 원래 파일 이름(확장자 제외)과 시각 읽기
+    fn parse(id: &str, name: &str) -> Option<(String, Self)> {
+        let (stem, stamp) = name.strip_suffix(".json")?.rsplit_once('.')?;
+        let (time, seq) = match stamp.split_once('-') {
+            Some((time, seq)) => (time, seq.parse().ok()?),
+            None => (stamp, 1),
+        };
+        let created_at = NaiveDateTime::parse_from_str(time, BACKUP_TIME_FORMAT).ok()?.and_utc();
+        let info = Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            created_at,
+            seq,
+        };
+        Some((stem.to_string(), info))
+    }
+}
+
+impl Storage {
+    pub(super) fn workspace_backup_dir(&self, id: &str) -> PathBuf {
+        self.workspace_dir().join("backups").join(id)
+    }
+
+    pub(super) fn universe_backup_dir(&self, id: &str) -> PathBuf {
+        self.data_dir.join("backups").join(id)
+    }
+
+    /// 덮어쓰기 전에 기존 파일을 백업 디렉토리에 복사하고 오래된 백업 정리
+    ///
+    /// 백업 수 제한은 원래 파일마다 따로 센다 (Universe 파일과 Graph 파일이 같은 디렉토리를 쓰므로).
+    pub(super) fn backup(&self, path: &Path, backup_dir: &Path) -> StorageResult<()> {
+        if self.backup_limit == 0 || !path.exists() {
+            return Ok(());
+        }
+        let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy().to_string()) else {
+            return Ok(());
+        };
+        create_dir(backup_dir)?;
+        // 같은 밀리초에 두 번 저장해도 앞의 백업을 덮어쓰지 않도록 번호를 붙인다
+        let time = Utc::now().format(BACKUP_TIME_FORMAT).to_string();
+        let mut seq = 1;
+        let backup_path = loop {
+            let stamp = if seq == 1 { time.clone() } else { format!("{}-{}", time, seq) };
+            let candidate = backup_dir.join(format!("{}.{}.json", stem, stamp));
+            if !candidate.exists() {
+                break candidate;
+            }
+            seq += 1;
+        };
+        fs::copy(path, &backup_path).map_err(|e| StorageError::io(&backup_path, e))?;
+
+        let backups = Self::backups_in(backup_dir, "")?;
+        let same_file = backups.iter().filter(|(backup_stem, _)| *backup_stem == stem);
+        for (_, old) in same_file.skip(self.backup_limit) {
+            let old_path = backup_dir.join(&old.name);
+            fs::remove_file(&old_path).map_err(|e| StorageError::io(&old_path, e))?;
+        }
+        Ok(())
+    }
+
+    // 백업 디렉토리의 백업들 (최근 것부터)
+    fn backups_in(backup_dir: &Path, id: &str) -> StorageResult<Vec<(String, BackupInfo)>> {
+        let mut backups: Vec<(String, BackupInfo)> = read_dir(backup_dir)?
+            .iter()
+            .filter_map(|entry| BackupInfo::parse(id, &entry.file_name().to_string_lossy()))
+            .collect();
+        backups.sort_by_key(|(_, info)| std::cmp::Reverse((info.created_at, info.seq)));
+        Ok(backups)
+    }
+
+    // 백업 하나의 내용과 원래 파일 이름 (확장자 제외)
+    fn read_backup(backup_dir: &Path, id: &str, name: &str) -> StorageResult<(String, Vec<u8>)> {
+        let (stem, _) = Self::backups_in(backup_dir, id)?
+            .into_iter()
+            .find(|(_, info)| info.name == name)
+            .ok_or_else(|| StorageError::NotFound(format!("{}/{}", id, name)))?;
+        let path = backup_dir.join(name);
+        let content = fs::read(&path).map_err(|e| StorageError::io(&path, e))?;
+        Ok((stem, content))
+    }
+
+    /// 워크스페이스의 백업 목록 (최근 것부터)
+    pub fn list_workspace_backups(&self, id: &str) -> StorageResult<Vec<BackupInfo>> {
+        let backups = Self::backups_in(&self.workspace_backup_dir(id), id)?;
+        Ok(backups.into_iter().map(|(_, info)| info).collect())
+    }
+
+    /// 워크스페이스를 백업 내용으로 되돌리기
+    ///
+    /// 현재 파일도 백업으로 남기고, 현재 저널은 예전 기록으로 옮긴다 (백업 시점 이후의 변경이므로).
+    pub fn restore_workspace_backup(&self, id: &str, name: &str) -> StorageResult<()> {
+        let backup_dir = self.workspace_backup_dir(id);
+        let (_, content) = Self::read_backup(&backup_dir, id, name)?;
+        create_dir(&self.workspace_dir())?;
+        self.backup(&self.workspace_path(id), &backup_dir)?;
+        write_file(&self.workspace_path(id), content)?;
+        self.compact_journal(id)
+    }
+
+    /// 유니버스의 백업 목록 (최근 것부터)
+    pub fn list_universe_backups(&self, id: &str) -> StorageResult<Vec<BackupInfo>> {
+        let backups = Self::backups_in(&self.universe_backup_dir(id), id)?;
+        Ok(backups.into_iter().map(|(_, info)| info).collect())
+    }
+
+    /// 유니버스를 백업 내용으로 되돌리기 (현재 파일도 백업으로 남기고 목록 색인도 갱신)
+    pub fn restore_universe_backup(&self, id: &str, name: &str) -> StorageResult<()> {
+        let backup_dir = self.universe_backup_dir(id);
+        let (stem, content) = Self::read_backup(&backup_dir, id, name)?;
+        // Universe 파일과 Graph 파일 중 원래 있던 쪽으로 되돌린다
+        if stem != id && stem != format!("graph_{}", id) {
+            return Err(StorageError::NotFound(format!("{}/{}", id, name)));
+        }
+        let path = self.data_dir.join(format!("{}.json", stem));
+        self.backup(&path, &backup_dir)?;
+        write_file(&path, content)?;
+        self.reindex_universe(id);
+        Ok(())
+    }
+}
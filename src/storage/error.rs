@@ -0,0 +1,98 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 저장소에서 읽거나 쓰지 못한 이유
+#[derive(Debug)]
+pub enum StorageError {
+    /// 파일을 읽거나 쓰는 중 입출력 오류
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// 파일 내용이 올바른 JSON이 아님 (줄/칸은 1부터, 모르면 0)
+    Parse {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// 저장할 값을 JSON으로 바꾸지 못함
+    Serialize(String),
+    /// 저장된 워크스페이스/유니버스가 없음
+    NotFound(String),
+    /// 이 버전에서 읽을 수 없는 파일 형식
+    VersionMismatch {
+        path: PathBuf,
+        found: u32,
+        supported: u32,
+    },
+    /// 파일이나 디렉토리에 접근 권한이 없음
+    PermissionDenied(PathBuf),
+    /// 쓸 수 있는 데이터 디렉토리가 없어 저장소를 열지 못함
+    Unavailable,
+}
+
+pub type StorageResult<T> = Result<T, StorageError>;
+
+impl StorageError {
+    /// 입출력 오류에 경로를 붙여 변환 (권한 오류는 따로 구분)
+    pub fn io(path: impl AsRef<Path>, source: io::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        if source.kind() == io::ErrorKind::PermissionDenied {
+            return StorageError::PermissionDenied(path);
+        }
+        StorageError::Io { path, source }
+    }
+
+    /// JSON 파싱 오류에 경로를 붙여 변환
+    pub fn parse(path: impl AsRef<Path>, error: serde_json::Error) -> Self {
+        // serde_json 메시지 끝의 위치 표시는 따로 보관하므로 뺀다
+        let position = format!(" at line {} column {}", error.line(), error.column());
+        let message = error.to_string();
+        StorageError::Parse {
+            path: path.as_ref().to_path_buf(),
+            line: error.line(),
+            column: error.column(),
+            message: message.strip_suffix(&position).unwrap_or(&message).to_string(),
+        }
+    }
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Io { path, source } => write!(f, "cannot access {}: {}", path.display(), source),
+            // 형식 변환 후에 난 오류는 위치를 알 수 없다 (0)
+            StorageError::Parse { path, line: 0, message, .. } => write!(f, "{} is corrupted: {}", path.display(), message),
+            StorageError::Parse { path, line, column, message } => write!(
+                f,
+                "{} is corrupted at line {}, column {}: {}",
+                path.display(),
+                line,
+                column,
+                message
+            ),
+            StorageError::Serialize(reason) => write!(f, "cannot serialize: {}", reason),
+            StorageError::NotFound(id) => write!(f, "'{}' not found", id),
+            StorageError::VersionMismatch { path, found, supported } => write!(
+                f,
+                "{} has format version {}, but only up to {} is supported",
+                path.display(),
+                found,
+                supported
+            ),
+            StorageError::PermissionDenied(path) => write!(f, "permission denied: {}", path.display()),
+            StorageError::Unavailable => write!(f, "no writable data directory"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
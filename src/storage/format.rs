@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use crate::celestial::{Node, NodeType, Position2D, Relation, RelationType};
+use super::{StorageError, StorageResult};
+
+/// 지금 저장하는 파일 형식 버전
+///
+/// - 0: 버전 헤더가 없는 Star/Planet 시절 유니버스 (노드에 층/3D 위치가 없음)
+/// - 1: 버전 헤더가 없는 파일 (`graph_<id>.json`은 그래프만, `<id>.json`은 Universe)
+/// - 2: `format_version` 헤더가 붙고, 그래프는 항상 `graph` 필드 아래에 있음
+pub const FORMAT_VERSION: u32 = 2;
+
+/// 이 버전부터는 케이크 밖 노드도 그대로 저장한 것 (더 예전 파일의 케이크 밖 노드는 불러올 때 케이크로 모은다)
+pub(super) const LOOSE_NODES_VERSION: u32 = 2;
+
+// MIGRATIONS[n]은 버전 n 파일을 n + 1로 바꾼다
+const MIGRATIONS: [fn(&mut Value); FORMAT_VERSION as usize] = [add_cake_fields, wrap_graph];
+
+/// 형식 버전 헤더를 붙여 저장하는 파일 내용
+#[derive(Serialize)]
+pub(super) struct Versioned<T> {
+    format_version: u32,
+    #[serde(flatten)]
+    data: T,
+}
+
+impl<T> Versioned<T> {
+    pub(super) fn new(data: T) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            data,
+        }
+    }
+}
+
+/// 유니버스 파일 (`<id>.json`, `graph_<id>.json` 모두 같은 형식)
+#[derive(Serialize, Deserialize)]
+pub(super) struct UniverseFile<G> {
+    #[serde(default)]
+    pub title: Option<String>,
+    pub graph: G,
+}
+
+/// 파일 내용의 형식 버전 (헤더가 없으면 내용을 보고 0 또는 1)
+pub fn format_version(value: &Value) -> u32 {
+    if let Some(version) = value.get("format_version").and_then(Value::as_u64) {
+        return version.try_into().unwrap_or(u32::MAX);
+    }
+    let has_legacy_node = graph_value(value)
+        .and_then(|graph| graph.get("nodes"))
+        .and_then(Value::as_object)
+        .is_some_and(|nodes| nodes.values().any(|node| node.get("layer").is_none()));
+    if has_legacy_node { 0 } else { 1 }
+}
+
+/// 예전 버전 파일 내용을 현재 형식으로 올림 (더 새로운 버전이면 오류)
+pub fn migrate(mut value: Value, path: &Path) -> StorageResult<Value> {
+    let found = format_version(&value);
+    if found > FORMAT_VERSION {
+        return Err(StorageError::VersionMismatch {
+            path: path.to_path_buf(),
+            found,
+            supported: FORMAT_VERSION,
+        });
+    }
+    for migration in &MIGRATIONS[found as usize..] {
+        migration(&mut value);
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("format_version".to_string(), FORMAT_VERSION.into());
+    }
+    Ok(value)
+}
+
+// 그래프 부분 (헤더가 없던 graph_ 파일은 파일 전체가 그래프)
+fn graph_value(value: &Value) -> Option<&Value> {
+    value.get("graph").or_else(|| value.get("nodes").is_some().then_some(value))
+}
+
+fn graph_value_mut(value: &mut Value) -> Option<&mut Value> {
+    if value.get("graph").is_some() {
+        value.get_mut("graph")
+    } else if value.get("nodes").is_some() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+// 0 → 1: 노드와 관계에 없는 필드를 새로 만든 노드/관계의 기본값으로 채움
+fn add_cake_fields(value: &mut Value) {
+    let Some(graph) = graph_value_mut(value) else {
+        return;
+    };
+
+    // 자식 목록은 parent_id에서 다시 만든다
+    let mut children: HashMap<String, Vec<Value>> = HashMap::new();
+    let mut created_at: HashMap<String, Value> = HashMap::new();
+    if let Some(nodes) = graph.get("nodes").and_then(Value::as_object) {
+        for (id, node) in nodes {
+            if let Some(parent_id) = node.get("parent_id").and_then(Value::as_str) {
+                children.entry(parent_id.to_string()).or_default().push(id.clone().into());
+            }
+            if let Some(time) = node.get("created_at") {
+                created_at.insert(id.clone(), time.clone());
+            }
+        }
+    }
+
+    if let Some(nodes) = graph.get_mut("nodes").and_then(Value::as_object_mut) {
+        for (id, node) in nodes.iter_mut() {
+            let Some(node) = node.as_object_mut() else {
+                continue;
+            };
+            let title = node.get("title").and_then(Value::as_str).unwrap_or_default().to_string();
+            let node_type: NodeType = node.get("node_type")
+                .and_then(|t| serde_json::from_value(t.clone()).ok())
+                .unwrap_or(NodeType::Note);
+            let position: Position2D = node.get("position")
+                .and_then(|p| serde_json::from_value(p.clone()).ok())
+                .unwrap_or(Position2D::new(0.0, 0.0));
+            node.entry("children_ids").or_insert_with(|| children.remove(id).unwrap_or_default().into());
+            fill_missing(node, &Node::new(title, node_type, position));
+        }
+    }
+
+    if let Some(relations) = graph.get_mut("relations").and_then(Value::as_array_mut) {
+        for relation in relations.iter_mut().filter_map(Value::as_object_mut) {
+            // 관계 생성 시각은 남아있지 않으므로 출발 노드의 생성 시각으로
+            let source_created = relation.get("source_id")
+                .and_then(Value::as_str)
+                .and_then(|id| created_at.get(id).cloned());
+            if let Some(time) = source_created {
+                relation.entry("created_at").or_insert(time);
+            }
+            let defaults = Relation::new(String::new(), String::new(), RelationType::Association);
+            fill_missing(relation, &defaults);
+        }
+    }
+}
+
+// 1 → 2: 그래프만 있던 파일을 `graph` 필드 아래로 옮김
+fn wrap_graph(value: &mut Value) {
+    if value.get("graph").is_none() && value.get("nodes").is_some() {
+        let graph = value.take();
+        *value = serde_json::json!({ "graph": graph });
+    }
+}
+
+fn fill_missing(object: &mut Map<String, Value>, defaults: &impl Serialize) {
+    if let Ok(Value::Object(defaults)) = serde_json::to_value(defaults) {
+        for (key, value) in defaults {
+            object.entry(key).or_insert(value);
+        }
+    }
+}
+
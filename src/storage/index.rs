@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use serde::de::IgnoredAny;
+use crate::celestial::Graph;
+use super::format::{UniverseFile, Versioned};
+use super::{create_dir, read_dir, read_versioned, to_json, write_file, Storage, StorageError, StorageResult};
+
+// 미리보기에 찍는 노드 수
+const THUMBNAIL_POINTS: usize = 64;
+
+/// 유니버스 목록에 보여줄 정보 (메타데이터 색인에 저장됨)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UniverseInfo {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    /// 파일이 마지막으로 바뀐 시각 (색인이 최신인지 확인하는 데도 씀)
+    pub modified_at: DateTime<Utc>,
+    pub node_count: usize,
+    pub relation_count: usize,
+    /// 노드들에 붙은 태그 전체
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    /// 노드 배치 미리보기 (0~1로 정규화한 위치, 최대 64개)
+    #[serde(default)]
+    pub thumbnail: Vec<[f32; 2]>,
+}
+
+impl UniverseInfo {
+    fn new(id: &str, title: String, graph: &Graph, created_at: DateTime<Utc>, modified_at: DateTime<Utc>) -> Self {
+        Self {
+            id: id.to_string(),
+            title,
+            created_at,
+            modified_at,
+            node_count: graph.node_count(),
+            relation_count: graph.relation_count(),
+            tags: graph.all_tags().into_iter().map(|(tag, _)| tag.to_string()).collect(),
+            thumbnail: thumbnail(graph),
+        }
+    }
+}
+
+// 노드 위치들을 0~1 범위로 (케이크에 속한 노드는 케이크 중심에서의 거리/각도로)
+fn thumbnail(graph: &Graph) -> Vec<[f32; 2]> {
+    // 노드가 많으면 먼저 만든 것부터 (HashMap 순서는 매번 달라 미리보기가 흔들리므로)
+    let mut nodes: Vec<_> = graph.get_nodes().collect();
+    nodes.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+    let points: Vec<[f32; 2]> = nodes.into_iter()
+        .take(THUMBNAIL_POINTS)
+        .map(|node| match graph.get_node_cake(&node.id) {
+            Some(cake) => [
+                cake.center.x + node.layer_radius * node.layer_angle.cos(),
+                cake.center.y + node.layer_radius * node.layer_angle.sin(),
+            ],
+            None => [node.position.x, node.position.y],
+        })
+        .collect();
+
+    let min = |axis: usize| points.iter().map(|p| p[axis]).fold(f32::INFINITY, f32::min);
+    let max = |axis: usize| points.iter().map(|p| p[axis]).fold(f32::NEG_INFINITY, f32::max);
+    let (min_x, min_y) = (min(0), min(1));
+    let span = (max(0) - min_x).max(max(1) - min_y).max(f32::EPSILON);
+    points.iter()
+        .map(|p| [(p[0] - min_x) / span, (p[1] - min_y) / span])
+        .collect()
+}
+
+/// 유니버스 파일마다 한 줄씩 들고 있는 메타데이터 색인
+///
+/// 저장할 때 갱신되고, 목록을 볼 때는 수정 시각이 달라진 파일만 다시 읽는다.
+#[derive(Default, Serialize, Deserialize)]
+struct UniverseIndex {
+    #[serde(default)]
+    universes: BTreeMap<String, UniverseInfo>,
+}
+
+impl Storage {
+    fn universe_index_path(&self) -> PathBuf {
+        self.data_dir.join("index").join("universes.json")
+    }
+
+    // 색인은 다시 만들 수 있으므로 없거나 읽을 수 없으면 빈 색인에서 시작
+    fn load_universe_index(&self, warnings: &mut Vec<StorageError>) -> UniverseIndex {
+        let path = self.universe_index_path();
+        if !path.exists() {
+            return UniverseIndex::default();
+        }
+        read_versioned(&path).unwrap_or_else(|e| {
+            warnings.push(e);
+            UniverseIndex::default()
+        })
+    }
+
+    fn save_universe_index(&self, index: &UniverseIndex) -> StorageResult<()> {
+        let path = self.universe_index_path();
+        if let Some(dir) = path.parent() {
+            create_dir(dir)?;
+        }
+        write_file(&path, to_json(&Versioned::new(index))?)
+    }
+
+    /// 방금 저장한 유니버스의 색인 갱신
+    ///
+    /// 색인을 못 써도 저장은 성공한 것이고, 색인과 수정 시각이 다른 파일은 다음 목록 때 다시 읽으므로 오류는 버린다.
+    pub(super) fn index_universe(&self, id: &str, title: Option<&str>, graph: &Graph, path: &Path) {
+        let _ = file_modified(path).and_then(|modified_at| {
+            let mut index = self.load_universe_index(&mut Vec::new());
+            let previous = index.universes.remove(id);
+            let title = title.map(str::to_string)
+                .or_else(|| previous.as_ref().map(|info| info.title.clone()))
+                .unwrap_or_else(|| format!("Universe {}", id));
+            let created_at = previous.map_or(modified_at, |info| info.created_at);
+            index.universes.insert(id.to_string(), UniverseInfo::new(id, title, graph, created_at, modified_at));
+            self.save_universe_index(&index)
+        });
+    }
+
+    /// 파일을 저장 말고 다른 방법으로 바꾼 뒤(백업 복원 등) 그 유니버스의 색인 갱신 (읽을 수 없으면 다음 목록 때 알린다)
+    pub(super) fn reindex_universe(&self, id: &str) {
+        let Some(path) = self.universe_path(id) else {
+            return;
+        };
+        if let Ok(file) = read_versioned::<UniverseFile<Graph>>(&path) {
+            let title = file.title.or_else(|| self.universe_file_title(id));
+            self.index_universe(id, title.as_deref(), &file.graph, &path);
+        }
+    }
+
+    pub(super) fn unindex_universe(&self, id: &str) -> StorageResult<()> {
+        let mut index = self.load_universe_index(&mut Vec::new());
+        if index.universes.remove(id).is_some() {
+            self.save_universe_index(&index)?;
+        }
+        Ok(())
+    }
+
+    // Graph 파일에는 제목이 없으므로 같은 ID의 Universe 파일에서 제목 찾기
+    fn universe_file_title(&self, id: &str) -> Option<String> {
+        let path = self.data_dir.join(format!("{}.json", id));
+        if !path.exists() {
+            return None;
+        }
+        read_versioned::<UniverseFile<IgnoredAny>>(&path).ok()?.title
+    }
+
+    // 색인 이후에 바뀐 파일이면 다시 읽은 정보 (색인이 최신이면 None)
+    fn read_universe_info(&self, id: &str, path: &Path, indexed: Option<&UniverseInfo>) -> StorageResult<Option<UniverseInfo>> {
+        let modified_at = file_modified(path)?;
+        if indexed.is_some_and(|info| info.modified_at == modified_at) {
+            return Ok(None);
+        }
+        let file: UniverseFile<Graph> = read_versioned(path)?;
+        let title = file.title
+            .or_else(|| self.universe_file_title(id))
+            .unwrap_or_else(|| format!("Universe {}", id));
+        let created_at = match indexed {
+            Some(info) => info.created_at,
+            None => file_created(path)?,
+        };
+        Ok(Some(UniverseInfo::new(id, title, &file.graph, created_at, modified_at)))
+    }
+
+    /// 유니버스 목록 (최근 생성 순)
+    ///
+    /// 색인에 없거나 색인 이후에 바뀐 파일만 열어서 읽고, 색인을 갱신한다.
+    /// 읽을 수 없는 파일은 목록에서 빼고 경고로 함께 돌려준다.
+    pub fn list_universes(&self) -> StorageResult<(Vec<UniverseInfo>, Vec<StorageError>)> {
+        let mut warnings = Vec::new();
+        // ID마다 불러올 파일 (load_universe처럼 Graph 파일이 우선)
+        let mut files: BTreeMap<String, PathBuf> = BTreeMap::new();
+        for entry in read_dir(&self.data_dir)? {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy().to_string()) else {
+                continue;
+            };
+            match stem.strip_prefix("graph_") {
+                Some(id) => {
+                    files.insert(id.to_string(), path);
+                }
+                None => {
+                    files.entry(stem).or_insert(path);
+                }
+            }
+        }
+
+        let mut index = self.load_universe_index(&mut warnings);
+        let mut changed = false;
+        index.universes.retain(|id, _| {
+            let keep = files.contains_key(id);
+            changed |= !keep;
+            keep
+        });
+
+        for (id, path) in &files {
+            match self.read_universe_info(id, path, index.universes.get(id)) {
+                Ok(Some(info)) => {
+                    index.universes.insert(id.clone(), info);
+                    changed = true;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    // 망가진 파일 하나 때문에 나머지 목록까지 못 보면 안 된다
+                    changed |= index.universes.remove(id).is_some();
+                    warnings.push(e);
+                }
+            }
+        }
+
+        if changed {
+            self.save_universe_index(&index)?;
+        }
+
+        let mut universes: Vec<UniverseInfo> = index.universes.into_values().collect();
+        universes.sort_by_key(|u| std::cmp::Reverse(u.created_at));
+        Ok((universes, warnings))
+    }
+}
+
+fn file_modified(path: &Path) -> StorageResult<DateTime<Utc>> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::from)
+        .map_err(|e| StorageError::io(path, e))
+}
+
+// 파일 생성 시각 (지원하지 않는 파일 시스템이면 수정 시각)
+fn file_created(path: &Path) -> StorageResult<DateTime<Utc>> {
+    let metadata = fs::metadata(path).map_err(|e| StorageError::io(path, e))?;
+    let time = metadata.created()
+        .or_else(|_| metadata.modified())
+        .map_err(|e| StorageError::io(path, e))?;
+    Ok(DateTime::from(time))
+}
@@ -1,166 +1,381 @@
+mod backup;
+mod error;
+mod format;
+mod index;
+
 use std::fs;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
 use crate::core::universe::Universe;
-use crate::celestial::Graph;
+use crate::celestial::{Graph, JournalEntry};
+
+pub use backup::BackupInfo;
+pub use error::{StorageError, StorageResult};
+pub use format::{format_version, migrate, FORMAT_VERSION};
+pub use index::UniverseInfo;
+use format::{UniverseFile, Versioned, LOOSE_NODES_VERSION};
+
+// 워크스페이스/유니버스마다 기본으로 남기는 백업 수
+const DEFAULT_BACKUP_LIMIT: usize = 5;
 
+/// 저장된 케이크 워크스페이스 정보
 #[derive(Clone, Debug)]
-pub struct UniverseInfo {
+pub struct WorkspaceInfo {
     pub id: String,
-    pub title: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub node_count: usize,
+    pub modified_at: chrono::DateTime<chrono::Utc>,
 }
 
 pub struct Storage {
     data_dir: PathBuf,
+    backup_limit: usize,
 }
 
 impl Storage {
-    pub fn new() -> Self {
+    /// 기본 데이터 디렉토리를 쓰는 저장소 (디렉토리를 만들 수 없으면 오류)
+    pub fn new() -> StorageResult<Self> {
         let data_dir = dirs::data_local_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("cosmos");
-        
-        // 데이터 디렉토리 생성
-        fs::create_dir_all(&data_dir).unwrap_or_else(|e| {
-            eprintln!("Failed to create data directory: {}", e);
-        });
+        Self::with_dir(data_dir)
+    }
 
-        Self { data_dir }
+    /// 지정한 디렉토리를 쓰는 저장소
+    pub fn with_dir(data_dir: impl Into<PathBuf>) -> StorageResult<Self> {
+        let data_dir = data_dir.into();
+        create_dir(&data_dir)?;
+        Ok(Self {
+            data_dir,
+            backup_limit: DEFAULT_BACKUP_LIMIT,
+        })
     }
 
-    pub fn save_universe(&self, universe: &Universe, id: &str) {
-        let file_path = self.data_dir.join(format!("{}.json", id));
-        
-        // Universe를 JSON으로 직렬화
-        if let Ok(json) = serde_json::to_string_pretty(universe) {
-            if let Err(e) = fs::write(&file_path, json) {
-                eprintln!("Failed to save universe: {}", e);
-            }
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// 저장할 때마다 남길 백업 수 (0이면 백업하지 않음)
+    pub fn set_backup_limit(&mut self, limit: usize) {
+        self.backup_limit = limit;
+    }
+
+    /// 워크스페이스 파일들이 저장되는 디렉토리
+    fn workspace_dir(&self) -> PathBuf {
+        self.data_dir.join("workspaces")
+    }
+
+    fn workspace_path(&self, id: &str) -> PathBuf {
+        self.workspace_dir().join(format!("{}.json", id))
+    }
+
+    /// 케이크 워크스페이스 저장 (이전 파일은 백업으로 남김)
+    pub fn save_workspace<T: Serialize>(&self, workspace: &T, id: &str) -> StorageResult<()> {
+        create_dir(&self.workspace_dir())?;
+        let json = to_json(&Versioned::new(workspace))?;
+        self.backup(&self.workspace_path(id), &self.workspace_backup_dir(id))?;
+        write_file(&self.workspace_path(id), json)
+    }
+
+    /// 케이크 워크스페이스 불러오기 (예전 형식이면 현재 형식으로 올려서)
+    pub fn load_workspace<T: DeserializeOwned>(&self, id: &str) -> StorageResult<T> {
+        let path = self.workspace_path(id);
+        if !path.exists() {
+            return Err(StorageError::NotFound(id.to_string()));
         }
+        read_versioned(&path)
     }
 
-    /// Graph 저장 (새로운 메서드)
-    pub fn save_graph(&self, graph: &Graph, id: &str) {
-        let file_path = self.data_dir.join(format!("graph_{}.json", id));
-        
-        if let Ok(json) = serde_json::to_string_pretty(graph) {
-            if let Err(e) = fs::write(&file_path, json) {
-                eprintln!("Failed to save graph: {}", e);
-            }
+    pub fn workspace_exists(&self, id: &str) -> bool {
+        self.workspace_path(id).exists()
+    }
+
+    // 마지막 스냅샷(워크스페이스 파일) 이후의 변경 기록
+    fn journal_path(&self, id: &str) -> PathBuf {
+        self.workspace_dir().join(format!("{}.journal.jsonl", id))
+    }
+
+    // 스냅샷에 합쳐진 예전 변경 기록 (조회용)
+    fn journal_archive_path(&self, id: &str) -> PathBuf {
+        self.workspace_dir().join(format!("{}.journal-archive.jsonl", id))
+    }
+
+    /// 워크스페이스 저널에 변경 추가 (한 줄에 항목 하나)
+    pub fn append_journal(&self, id: &str, entries: &[JournalEntry]) -> StorageResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        create_dir(&self.workspace_dir())?;
+        let path = self.journal_path(id);
+        let mut lines = String::new();
+        // 쓰는 도중 끊긴 줄이 있으면 새 항목이 그 뒤에 붙지 않도록 줄을 바꾼다
+        if !ends_with_newline(&path) {
+            lines.push('\n');
+        }
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry).map_err(|e| StorageError::Serialize(e.to_string()))?);
+            lines.push('\n');
         }
+        append_file(&path, lines.as_bytes())
     }
 
-    pub fn load_universe(&self, id: &str) -> Result<Graph, Box<dyn std::error::Error>> {
-        // 먼저 Graph 파일 형식으로 시도
-        let graph_path = self.data_dir.join(format!("graph_{}.json", id));
-        if graph_path.exists() {
-            let json = fs::read_to_string(&graph_path)?;
-            let graph: Graph = serde_json::from_str(&json)?;
-            return Ok(graph);
+    /// 마지막 스냅샷 이후의 저널 항목들 (워크스페이스를 불러온 뒤 replay할 것)
+    ///
+    /// 읽을 수 없어 건너뛴 줄은 경고로 함께 돌려준다.
+    pub fn load_journal(&self, id: &str) -> StorageResult<(Vec<JournalEntry>, Vec<StorageError>)> {
+        let mut warnings = Vec::new();
+        let entries = read_journal(&self.journal_path(id), &mut warnings)?;
+        Ok((entries, warnings))
+    }
+
+    /// 예전 기록까지 포함한 모든 저널 항목 (오래된 순, 건너뛴 줄은 경고로)
+    pub fn load_journal_history(&self, id: &str) -> StorageResult<(Vec<JournalEntry>, Vec<StorageError>)> {
+        let mut warnings = Vec::new();
+        let mut entries = read_journal(&self.journal_archive_path(id), &mut warnings)?;
+        entries.extend(read_journal(&self.journal_path(id), &mut warnings)?);
+        Ok((entries, warnings))
+    }
+
+    /// 워크스페이스 파일을 새로 저장한 뒤 호출: 저널을 예전 기록으로 옮기고 비운다
+    pub fn compact_journal(&self, id: &str) -> StorageResult<()> {
+        let path = self.journal_path(id);
+        if !path.exists() {
+            return Ok(());
         }
-        
-        // 기존 Universe 파일 형식으로 시도 (하위 호환성)
-        let universe_path = self.data_dir.join(format!("{}.json", id));
-        if universe_path.exists() {
-            let json = fs::read_to_string(&universe_path)?;
-            let _universe: Universe = serde_json::from_str(&json)?;
-            // Universe를 Graph로 변환하는 로직이 필요하지만, 임시로 새 Graph 반환
-            return Ok(Graph::new());
+        let mut journal = fs::read(&path).map_err(|e| StorageError::io(&path, e))?;
+        if journal.last().is_some_and(|b| *b != b'\n') {
+            journal.push(b'\n');
         }
-        
-        Err("Universe not found".into())
+        let archive_path = self.journal_archive_path(id);
+        let mut archive = if archive_path.exists() {
+            fs::read(&archive_path).map_err(|e| StorageError::io(&archive_path, e))?
+        } else {
+            Vec::new()
+        };
+        // 지난번에 예전 기록은 썼지만 저널을 지우기 전에 멈췄다면 같은 항목을 또 붙이지 않는다
+        if !archive.ends_with(&journal) {
+            if archive.last().is_some_and(|b| *b != b'\n') {
+                archive.push(b'\n');
+            }
+            archive.extend_from_slice(&journal);
+            write_file(&archive_path, &archive)?;
+        }
+        fs::remove_file(&path).map_err(|e| StorageError::io(&path, e))
     }
 
-    /// UniverseInfo 목록 반환
-    pub fn list_universes(&self) -> Vec<UniverseInfo> {
-        let mut universes = Vec::new();
-        
-        if let Ok(entries) = fs::read_dir(&self.data_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if let Some(extension) = path.extension() {
-                    if extension == "json" {
-                        if let Some(file_name) = path.file_stem() {
-                            let file_name_str = file_name.to_string_lossy();
-                            
-                            // graph_ 접두사 제거
-                            let id = if file_name_str.starts_with("graph_") {
-                                file_name_str.strip_prefix("graph_").unwrap_or(&file_name_str)
-                            } else {
-                                &file_name_str
-                            };
-                            
-                            // 메타데이터 수집
-                            let node_count = self.get_node_count(&path);
-                            let created_at = self.get_file_created_time(&path);
-                            
-                            universes.push(UniverseInfo {
-                                id: id.to_string(),
-                                title: format!("Universe {}", id),
-                                created_at,
-                                node_count,
-                            });
-                        }
-                    }
-                }
+    /// 저장된 워크스페이스 목록 (최근 수정 순)
+    pub fn list_workspaces(&self) -> StorageResult<Vec<WorkspaceInfo>> {
+        let mut workspaces = Vec::new();
+        for entry in read_dir(&self.workspace_dir())? {
+            let path = entry.path();
+            // 저널(.jsonl)은 워크스페이스가 아님
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
             }
+            let Some(id) = path.file_stem().map(|stem| stem.to_string_lossy().to_string()) else {
+                continue;
+            };
+            let modified_at = entry.metadata()
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| StorageError::io(&path, e))?
+                .into();
+            workspaces.push(WorkspaceInfo { id, modified_at });
         }
-        
-        // 생성일 기준 내림차순 정렬
-        universes.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-        universes
+
+        workspaces.sort_by_key(|w| std::cmp::Reverse(w.modified_at));
+        Ok(workspaces)
+    }
+
+    pub fn save_universe(&self, universe: &Universe, id: &str) -> StorageResult<()> {
+        let file_path = self.data_dir.join(format!("{}.json", id));
+        let json = to_json(&Versioned::new(universe))?;
+        self.backup(&file_path, &self.universe_backup_dir(id))?;
+        write_file(&file_path, json)?;
+        self.index_universe(id, Some(&universe.title), &universe.graph, &file_path);
+        Ok(())
+    }
+
+    /// Graph 저장 (새로운 메서드)
+    pub fn save_graph(&self, graph: &Graph, id: &str) -> StorageResult<()> {
+        let file_path = self.data_dir.join(format!("graph_{}.json", id));
+        let json = to_json(&Versioned::new(UniverseFile { title: None, graph }))?;
+        self.backup(&file_path, &self.universe_backup_dir(id))?;
+        write_file(&file_path, json)?;
+        self.index_universe(id, None, graph, &file_path);
+        Ok(())
     }
 
-    pub fn get_universe_list(&self) -> impl Iterator<Item = Universe> {
-        fs::read_dir(&self.data_dir)
+    // 유니버스를 불러올 파일 (Graph 파일이 있으면 그쪽, 없으면 Universe 파일)
+    fn universe_path(&self, id: &str) -> Option<PathBuf> {
+        [format!("graph_{}.json", id), format!("{}.json", id)]
             .into_iter()
-            .flatten()
-            .filter_map(|entry| {
-                let entry = entry.ok()?;
-                let path = entry.path();
-                if path.extension()? == "json" {
-                    fs::read_to_string(path)
-                        .ok()
-                        .and_then(|json| serde_json::from_str(&json).ok())
-                } else {
-                    None
+            .map(|name| self.data_dir.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// 유니버스 불러오기 (Graph 파일이 있으면 그쪽, 없으면 Universe 파일)
+    ///
+    /// 두 파일 모두 같은 형식이고, 예전 버전 파일은 현재 형식으로 올려서 읽는다.
+    pub fn load_universe(&self, id: &str) -> StorageResult<Graph> {
+        let path = self.universe_path(id).ok_or_else(|| StorageError::NotFound(id.to_string()))?;
+        let (version, file): (u32, UniverseFile<Graph>) = read_versioned_from(&path)?;
+        let mut graph = if version < LOOSE_NODES_VERSION {
+            // 케이크가 없던 시절에 저장된 노드들은 제목을 딴 케이크로 모은다
+            let title = file.title.unwrap_or_else(|| format!("Universe {}", id));
+            Universe::new(id.to_string(), title, file.graph).into_cake_graph()
+        } else {
+            file.graph
+        };
+        // 이전 버전에서 저장된 계층 정보 정리
+        graph.repair_hierarchy();
+        Ok(graph)
+    }
+
+    /// 기존 Universe 형식으로 저장된 파일들 (graph_ 파일 제외, 읽을 수 없는 파일은 건너뛰고 경고로 돌려줌)
+    pub fn get_universe_list(&self) -> StorageResult<(Vec<Universe>, Vec<StorageError>)> {
+        let mut universes = Vec::new();
+        let mut warnings = Vec::new();
+        for entry in read_dir(&self.data_dir)? {
+            let path = entry.path();
+            let is_universe = path.extension().is_some_and(|ext| ext == "json")
+                && path.file_stem().is_some_and(|stem| !stem.to_string_lossy().starts_with("graph_"));
+            if is_universe {
+                match read_versioned(&path) {
+                    Ok(universe) => universes.push(universe),
+                    Err(e) => warnings.push(e),
                 }
-            })
+            }
+        }
+        Ok((universes, warnings))
     }
 
-    pub fn delete_universe(&self, id: &str) -> bool {
+    /// 유니버스 파일 삭제 (두 형식 모두)
+    pub fn delete_universe(&self, id: &str) -> StorageResult<()> {
         let file_path = self.data_dir.join(format!("{}.json", id));
         let graph_path = self.data_dir.join(format!("graph_{}.json", id));
-        
-        let mut success = false;
-        if file_path.exists() {
-            success |= fs::remove_file(file_path).is_ok();
+
+        let mut found = false;
+        for path in [file_path, graph_path] {
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| StorageError::io(&path, e))?;
+                found = true;
+            }
         }
-        if graph_path.exists() {
-            success |= fs::remove_file(graph_path).is_ok();
+
+        if !found {
+            return Err(StorageError::NotFound(id.to_string()));
         }
-        
-        success
+        self.unindex_universe(id)
     }
+}
 
-    /// 파일에서 노드 개수 추출 (근사치)
-    fn get_node_count(&self, path: &PathBuf) -> usize {
-        if let Ok(content) = fs::read_to_string(path) {
-            // JSON에서 "nodes" 필드의 개수를 대략적으로 계산
-            content.matches("\"id\":").count()
-        } else {
-            0
-        }
+fn create_dir(path: &Path) -> StorageResult<()> {
+    fs::create_dir_all(path).map_err(|e| StorageError::io(path, e))
+}
+
+fn read_file(path: &Path) -> StorageResult<String> {
+    fs::read_to_string(path).map_err(|e| StorageError::io(path, e))
+}
+
+/// 임시 파일에 끝까지 쓰고 디스크에 반영한 뒤 이름을 바꿔 교체
+///
+/// 쓰는 도중 프로그램이 죽거나 디스크가 가득 차도 기존 파일은 그대로 남는다.
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> StorageResult<()> {
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+    let written = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_ref())?;
+            file.sync_all()
+        })
+        .map_err(|e| StorageError::io(&temp_path, e))
+        .and_then(|()| fs::rename(&temp_path, path).map_err(|e| StorageError::io(path, e)));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_path);
     }
+    written?;
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    sync_dir(dir)
+}
+
+// 이름 바꾸기가 디스크에 남도록 디렉토리도 반영 (디렉토리를 열 수 없는 플랫폼은 생략)
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> StorageResult<()> {
+    fs::File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| StorageError::io(dir, e))
+}
 
-    /// 파일 생성 시간 가져오기
-    fn get_file_created_time(&self, path: &PathBuf) -> chrono::DateTime<chrono::Utc> {
-        if let Ok(metadata) = fs::metadata(path) {
-            if let Ok(created) = metadata.created() {
-                return chrono::DateTime::from(created);
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> StorageResult<()> {
+    Ok(())
+}
+
+fn read_journal(path: &Path, warnings: &mut Vec<StorageError>) -> StorageResult<Vec<JournalEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = read_file(path)?;
+    let entries = content.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                // 쓰는 도중 끊긴 줄은 건너뛴다
+                warnings.push(StorageError::parse(path, e));
+                None
             }
-        }
-        chrono::Utc::now()
+        })
+        .collect();
+    Ok(entries)
+}
+
+// 파일이 줄바꿈으로 끝나는지 (없거나 비어있으면 true, 파일 전체를 읽지 않고 마지막 바이트만 봄)
+fn ends_with_newline(path: &Path) -> bool {
+    let mut last = [0u8];
+    match fs::File::open(path) {
+        Ok(mut file) => file.seek(SeekFrom::End(-1)).and_then(|_| file.read_exact(&mut last)).map_or(true, |()| last[0] == b'\n'),
+        Err(_) => true,
     }
-} 
\ No newline at end of file
+}
+
+fn append_file(path: &Path, contents: &[u8]) -> StorageResult<()> {
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents))
+        .map_err(|e| StorageError::io(path, e))
+}
+
+// 디렉토리 안의 항목들 (디렉토리가 아직 없으면 비어있음)
+fn read_dir(path: &Path) -> StorageResult<Vec<fs::DirEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    fs::read_dir(path)
+        .and_then(|entries| entries.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| StorageError::io(path, e))
+}
+
+fn to_json<T: Serialize>(value: &T) -> StorageResult<String> {
+    serde_json::to_string_pretty(value).map_err(|e| StorageError::Serialize(e.to_string()))
+}
+
+// 파일을 읽어 현재 형식으로 올린 뒤 변환
+fn read_versioned<T: DeserializeOwned>(path: &Path) -> StorageResult<T> {
+    read_versioned_from(path).map(|(_, data)| data)
+}
+
+// read_versioned와 같고, 파일에 원래 적혀 있던 형식 버전도 함께
+fn read_versioned_from<T: DeserializeOwned>(path: &Path) -> StorageResult<(u32, T)> {
+    let json = read_file(path)?;
+    let value: Value = serde_json::from_str(&json).map_err(|e| StorageError::parse(path, e))?;
+    let version = format_version(&value);
+    let data = serde_json::from_value(migrate(value, path)?).map_err(|e| {
+        // 현재 형식 파일이면 원문에서 다시 읽어 오류 위치를 찾는다
+        let located = (version == FORMAT_VERSION).then(|| serde_json::from_str::<T>(&json).err()).flatten();
+        StorageError::parse(path, located.unwrap_or(e))
+    })?;
+    Ok((version, data))
+}
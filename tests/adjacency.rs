@@ -0,0 +1,36 @@
+use cosmos::celestial::{Graph, NodeType, RelationType};
+
+fn ids<'a>(relations: impl Iterator<Item = &'a cosmos::celestial::Relation>) -> Vec<&'a str> {
+    relations.map(|r| r.id.as_str()).collect()
+}
+
+#[test]
+fn removing_middle_relation_keeps_moved_relation_indexed() {
+    let mut graph = Graph::new();
+    let [a, b, c, d]: [String; 4] = ["A", "B", "C", "D"]
+        .map(|title| graph.create_node_3d(title.to_string(), NodeType::Concept, 1, 100.0, 0.0).unwrap());
+    let first = graph.add_relation(&a, &b, RelationType::Reference).unwrap();
+    let middle = graph.add_relation(&b, &c, RelationType::Reference).unwrap();
+    let last = graph.add_relation(&c, &d, RelationType::Association).unwrap();
+    let second_from_c = graph.add_relation(&c, &a, RelationType::Reference).unwrap();
+
+    // 가운데 관계를 지우면 마지막 관계(second_from_c)가 그 자리로 옮겨온다
+    graph.remove_relation(&middle).unwrap();
+    assert!(graph.get_relation(&middle).is_none());
+    assert_eq!(graph.get_relation(&second_from_c).unwrap().source_id, c);
+    assert_eq!(graph.get_relation(&last).unwrap().target_id, d);
+
+    // 옮겨온 관계의 끝점들에서 본 관계는 그대로, 추가된 순서도 유지
+    assert_eq!(ids(graph.outgoing_relations(&c)), [last.as_str(), second_from_c.as_str()]);
+    assert_eq!(ids(graph.incoming_relations(&a)), [second_from_c.as_str()]);
+    assert_eq!(ids(graph.outgoing_relations_of_type(&c, &RelationType::Reference)), [second_from_c.as_str()]);
+    assert_eq!(ids(graph.incoming_relations_of_type(&d, &RelationType::Association)), [last.as_str()]);
+    assert!(graph.outgoing_relations(&b).next().is_none());
+    assert!(graph.incoming_relations(&c).next().is_none());
+    assert_eq!(ids(graph.outgoing_relations(&a)), [first.as_str()]);
+
+    // 옮겨온 관계도 제자리에서 지울 수 있다
+    assert_eq!(graph.remove_relation(&second_from_c).unwrap().target_id, a);
+    assert_eq!(ids(graph.get_relations()), [first.as_str(), last.as_str()]);
+    assert!(graph.incoming_relations(&a).next().is_none());
+}
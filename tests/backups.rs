@@ -0,0 +1,43 @@
+use cosmos::celestial::{Graph, NodeType};
+use cosmos::core::universe::Universe;
+use cosmos::storage::Storage;
+
+fn temp_storage() -> Storage {
+    Storage::with_dir(std::env::temp_dir().join(format!("cosmos-test-{}", uuid::Uuid::new_v4()))).unwrap()
+}
+
+fn graph_with(count: usize) -> Graph {
+    let mut graph = Graph::new();
+    for i in 0..count {
+        graph.create_node_3d(format!("Node {}", i), NodeType::Concept, 1, 100.0, i as f32).unwrap();
+    }
+    graph
+}
+
+#[test]
+fn backups_are_pruned_per_file_and_restored() {
+    let mut storage = temp_storage();
+    storage.set_backup_limit(2);
+
+    // 연달아 저장해도 (같은 밀리초여도) 백업이 서로 덮어쓰지 않는다
+    for count in 0..4 {
+        storage.save_graph(&graph_with(count), "plan").unwrap();
+    }
+    let backups = storage.list_universe_backups("plan").unwrap();
+    assert_eq!(backups.len(), 2);
+    assert!(backups[0].name != backups[1].name);
+
+    // Universe 파일 백업은 Graph 파일 백업 수에 들어가지 않는다
+    let universe = Universe::new("plan".to_string(), "Plan".to_string(), graph_with(1));
+    storage.save_universe(&universe, "plan").unwrap();
+    storage.save_universe(&universe, "plan").unwrap();
+    let backups = storage.list_universe_backups("plan").unwrap();
+    assert_eq!(backups.len(), 3);
+    assert_eq!(backups.iter().filter(|b| b.name.starts_with("graph_plan.")).count(), 2);
+
+    // 가장 최근 Graph 백업은 노드 2개짜리
+    let latest = backups.iter().find(|b| b.name.starts_with("graph_plan.")).unwrap();
+    storage.restore_universe_backup("plan", &latest.name).unwrap();
+    assert_eq!(storage.load_universe("plan").unwrap().node_count(), 2);
+    assert_eq!(storage.list_universes().unwrap().0[0].node_count, 2);
+}
@@ -0,0 +1,68 @@
+use chrono::{Duration, TimeZone, Utc};
+use cosmos::celestial::{EnforcementMode, Graph, GraphError, NodeType, RelationType};
+
+// 만든 시각을 분 단위로 정해서 노드 만들기
+fn nodes(graph: &mut Graph, node_type: NodeType, minutes: &[i64]) -> Vec<String> {
+    minutes.iter()
+        .enumerate()
+        .map(|(i, minutes)| {
+            let id = graph.create_node_3d(format!("{} {}", node_type.name(), i), node_type.clone(), 2, 100.0, 0.0).unwrap();
+            graph.get_node_mut(&id).unwrap().created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(*minutes);
+            id
+        })
+        .collect()
+}
+
+#[test]
+fn closing_dependency_cycle_is_rejected_under_strict() {
+    let mut graph = Graph::new();
+    let ids = nodes(&mut graph, NodeType::Task, &[0, 1, 2]);
+    let (a, b, c) = (&ids[0], &ids[1], &ids[2]);
+    graph.add_relation(a, b, RelationType::Dependency).unwrap();
+    graph.add_relation(b, c, RelationType::Dependency).unwrap();
+
+    let result = graph.add_relation(c, a, RelationType::Dependency);
+    assert_eq!(result, Err(GraphError::DependencyCycle(vec![c.clone(), a.clone(), b.clone(), c.clone()])));
+    assert_eq!(graph.relation_count(), 2);
+    assert_eq!(graph.find_dependency_cycle(), None);
+}
+
+#[test]
+fn warn_mode_allows_cycle_and_reports_it() {
+    let mut graph = Graph::new();
+    graph.set_dependency_mode(EnforcementMode::Warn);
+    let ids = nodes(&mut graph, NodeType::Task, &[0, 1, 2]);
+    let (a, b, c) = (&ids[0], &ids[1], &ids[2]);
+    graph.add_relation(a, b, RelationType::Dependency).unwrap();
+    graph.add_relation(b, c, RelationType::Dependency).unwrap();
+    assert!(graph.take_warnings().is_empty());
+
+    graph.add_relation(c, a, RelationType::Dependency).unwrap();
+    assert_eq!(graph.take_warnings(), [GraphError::DependencyCycle(vec![c.clone(), a.clone(), b.clone(), c.clone()])]);
+    assert!(graph.take_warnings().is_empty());
+
+    // 먼저 만든 노드에서 시작하는 순환
+    assert_eq!(graph.find_dependency_cycle(), Some(vec![a.clone(), b.clone(), c.clone(), a.clone()]));
+    assert!(matches!(graph.task_order(), Err(GraphError::DependencyCycle(_))));
+}
+
+#[test]
+fn task_order_puts_dependencies_first_then_oldest() {
+    let mut graph = Graph::new();
+    // t1은 가장 늦게, t4는 가장 먼저 만들었다
+    let tasks = nodes(&mut graph, NodeType::Task, &[3, 2, 1, 0]);
+    let concept = nodes(&mut graph, NodeType::Concept, &[5]).remove(0);
+    let (t1, t2, t3, t4) = (&tasks[0], &tasks[1], &tasks[2], &tasks[3]);
+    graph.add_relation(t2, t1, RelationType::Dependency).unwrap();
+    graph.add_relation(t3, t1, RelationType::Dependency).unwrap();
+    graph.add_relation(t4, t3, RelationType::Dependency).unwrap();
+    graph.add_relation(t1, &concept, RelationType::Dependency).unwrap();
+
+    let expected = [t1.clone(), t3.clone(), t4.clone(), t2.clone()];
+    for _ in 0..3 {
+        let order: Vec<String> = graph.task_order().unwrap().into_iter().map(|n| n.id.clone()).collect();
+        assert_eq!(order, expected);
+    }
+    let all: Vec<String> = graph.dependency_order(None).unwrap().into_iter().map(|n| n.id.clone()).collect();
+    assert_eq!(all.first(), Some(&concept));
+}
@@ -0,0 +1,52 @@
+use chrono::{Duration, TimeZone, Utc};
+use cosmos::celestial::{Graph, Node, NodeType};
+
+fn set_created(graph: &mut Graph, id: &str, minutes: i64) {
+    let at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(minutes);
+    graph.get_node_mut(id).unwrap().created_at = at;
+}
+
+fn titles(nodes: &[&Node]) -> Vec<String> {
+    nodes.iter().map(|n| n.title.clone()).collect()
+}
+
+// v1 → v2a → v3, v1 → v2b (v2a를 먼저 만듦)
+fn branching() -> (Graph, [String; 4]) {
+    let mut graph = Graph::new();
+    let v1 = graph.create_node_3d("v1".to_string(), NodeType::Concept, 1, 100.0, 0.0).unwrap();
+    let v2a = graph.evolve_node(&v1, "v2a".to_string(), None).unwrap();
+    let v2b = graph.evolve_node(&v1, "v2b".to_string(), None).unwrap();
+    let v3 = graph.evolve_node(&v2a, "v3".to_string(), None).unwrap();
+    for (minutes, id) in [&v1, &v2a, &v2b, &v3].into_iter().enumerate() {
+        set_created(&mut graph, id, minutes as i64);
+    }
+    (graph, [v1, v2a, v2b, v3])
+}
+
+#[test]
+fn branching_evolution_reports_leaves_branches_and_common_ancestor() {
+    let (graph, [v1, v2a, v2b, v3]) = branching();
+
+    assert_eq!(titles(&graph.evolution_successors(&v1)), ["v2a", "v2b"]);
+    assert_eq!(titles(&graph.latest_versions(&v1)), ["v2b", "v3"]);
+    assert_eq!(titles(&graph.latest_versions(&v3)), ["v3"]);
+    let branches: Vec<Vec<String>> = graph.evolution_branches(&v1).iter().map(|b| titles(b)).collect();
+    assert_eq!(branches, [vec!["v1", "v2a", "v3"], vec!["v1", "v2b"]]);
+
+    assert_eq!(graph.evolution_root(&v3).unwrap().id, v1);
+    assert_eq!(titles(&graph.evolution_ancestors(&v3)), ["v2a", "v1"]);
+    assert_eq!(graph.common_evolution_ancestor(&v3, &v2b).unwrap().id, v1);
+    assert_eq!(graph.common_evolution_ancestor(&v3, &v2a).unwrap().id, v2a);
+}
+
+#[test]
+fn evolution_chain_follows_oldest_successor() {
+    let (mut graph, [v1, v2a, v2b, _]) = branching();
+    assert_eq!(titles(&graph.get_evolution_chain(&v1)), ["v1", "v2a", "v3"]);
+
+    // 추가한 순서가 아니라 만든 시각으로 갈래를 고른다
+    set_created(&mut graph, &v2b, 1);
+    set_created(&mut graph, &v2a, 2);
+    assert_eq!(titles(&graph.get_evolution_chain(&v1)), ["v1", "v2b"]);
+    assert_eq!(titles(&graph.evolution_successors(&v1)), ["v2b", "v2a"]);
+}
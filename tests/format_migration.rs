@@ -0,0 +1,230 @@
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use serde_json::Value;
+use cosmos::celestial::{Graph, NodeType, RelationType};
+use cosmos::storage::{format_version, migrate, Storage, StorageError, FORMAT_VERSION};
+
+fn fixture_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+fn fixture(name: &str) -> Value {
+    serde_json::from_str(&std::fs::read_to_string(fixture_path(name)).unwrap()).unwrap()
+}
+
+// 픽스처 파일 하나를 `file_name`으로 넣어둔 빈 저장소
+fn storage_with(fixture_name: &str, file_name: &str) -> Storage {
+    let dir = std::env::temp_dir().join(format!("cosmos-test-{}", uuid::Uuid::new_v4()));
+    let storage = Storage::with_dir(&dir).unwrap();
+    let target = dir.join(file_name);
+    std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+    std::fs::copy(fixture_path(fixture_name), target).unwrap();
+    storage
+}
+
+fn node_by_title<'a>(graph: &'a Graph, title: &str) -> &'a cosmos::celestial::Node {
+    graph.get_nodes().find(|n| n.title == title).unwrap()
+}
+
+#[test]
+fn detects_version_of_each_fixture() {
+    assert_eq!(format_version(&fixture("v0_universe.json")), 0);
+    assert_eq!(format_version(&fixture("v1_graph.json")), 1);
+    assert_eq!(format_version(&fixture("v1_universe.json")), 1);
+    assert_eq!(format_version(&fixture("v1_loose_universe.json")), 1);
+    assert_eq!(format_version(&fixture("v1_workspace.json")), 1);
+    assert_eq!(format_version(&fixture("v2_universe.json")), 2);
+}
+
+#[test]
+fn migrates_legacy_star_planet_universe() {
+    let storage = storage_with("v0_universe.json", "legacy.json");
+    let graph = storage.load_universe("legacy").unwrap();
+
+    assert_eq!(graph.node_count(), 2);
+    assert_eq!(graph.relation_count(), 1);
+    let star = node_by_title(&graph, "New Star");
+    let planet = node_by_title(&graph, "New Planet");
+    assert_eq!(star.node_type, NodeType::Star);
+    assert_eq!(planet.parent_id.as_deref(), Some(star.id.as_str()));
+    assert_eq!(star.children_ids, vec![planet.id.clone()]);
+    assert_eq!(planet.layer, NodeType::Planet.cake_layer());
+    assert_eq!(planet.position.x, 564.9961);
+    assert_eq!(planet.created_at.to_rfc3339(), "2024-12-18T17:20:23.174427+00:00");
+}
+
+#[test]
+fn legacy_universe_nodes_are_gathered_into_titled_cake() {
+    let storage = storage_with("v0_universe.json", "legacy.json");
+    let graph = storage.load_universe("legacy").unwrap();
+
+    assert_eq!(graph.cake_count(), 1);
+    let cake = graph.get_cakes().next().unwrap();
+    assert_eq!(cake.title, "999");
+    assert_eq!(graph.get_cake_nodes(&cake.id).len(), 2);
+    assert_eq!(cake.layer_count(), NodeType::Planet.cake_layer() + 1);
+
+    // 케이크 중심에서의 거리/각도로 원래 위치를 다시 계산할 수 있다
+    let planet = node_by_title(&graph, "New Planet");
+    let x = cake.center.x + planet.layer_radius * planet.layer_angle.cos();
+    let y = cake.center.y + planet.layer_radius * planet.layer_angle.sin();
+    assert!((x - 564.9961).abs() < 0.01);
+    assert!((y - 322.78906).abs() < 0.01);
+}
+
+// 케이크 중심과 노드의 거리/각도로 다시 계산한 캔버스 위치
+fn cake_position(graph: &Graph, title: &str) -> (f32, f32) {
+    let node = node_by_title(graph, title);
+    let cake = graph.get_cake(node.cake_id.as_deref().unwrap()).unwrap();
+    (
+        cake.center.x + node.layer_radius * node.layer_angle.cos(),
+        cake.center.y + node.layer_radius * node.layer_angle.sin(),
+    )
+}
+
+#[test]
+fn legacy_star_planet_file_keeps_title_relations_and_layout() {
+    let storage = storage_with("v0_universe.json", "legacy.json");
+    let graph = storage.load_universe("legacy").unwrap();
+
+    assert_eq!(graph.get_cakes().map(|c| c.title.as_str()).collect::<Vec<_>>(), ["999"]);
+    let star = node_by_title(&graph, "New Star");
+    let planet = node_by_title(&graph, "New Planet");
+    let relations: Vec<_> = graph.get_relations()
+        .map(|r| (r.id.as_str(), r.source_id.as_str(), r.target_id.as_str(), &r.relation_type))
+        .collect();
+    assert_eq!(relations, [("f8140e7e-10bb-4001-b630-a173e1dd431e", star.id.as_str(), planet.id.as_str(), &RelationType::Hierarchy)]);
+
+    // 두 노드 사이의 간격이 예전 파일과 같다
+    let (star_x, star_y) = cake_position(&graph, "New Star");
+    let (planet_x, planet_y) = cake_position(&graph, "New Planet");
+    assert!((planet_x - star_x - (564.9961 - 335.79688)).abs() < 0.01);
+    assert!((planet_y - star_y - (322.78906 - 300.91016)).abs() < 0.01);
+}
+
+#[test]
+fn unversioned_universe_without_cakes_is_gathered_into_titled_cake() {
+    let storage = storage_with("v1_loose_universe.json", "solar.json");
+    let graph = storage.load_universe("solar").unwrap();
+
+    assert_eq!(graph.get_cakes().map(|c| c.title.as_str()).collect::<Vec<_>>(), ["Solar"]);
+    assert!(graph.get_nodes().all(|n| n.cake_id.is_some()));
+    assert_eq!(graph.relation_count(), 1);
+    let (x, y) = cake_position(&graph, "Login");
+    assert!((x - 420.0).abs() < 0.01 && (y - 260.0).abs() < 0.01);
+    let (x, y) = cake_position(&graph, "Product");
+    assert!((x - 300.0).abs() < 0.01 && (y - 200.0).abs() < 0.01);
+}
+
+#[test]
+fn migrated_legacy_relation_takes_source_creation_time() {
+    let value = migrate(fixture("v0_universe.json"), Path::new("v0_universe.json")).unwrap();
+    let relation = &value["graph"]["relations"][0];
+    assert_eq!(relation["created_at"], "2024-12-18T17:20:22.241629Z");
+    assert_eq!(value["format_version"], FORMAT_VERSION);
+}
+
+#[test]
+fn migrates_unversioned_graph_file() {
+    let storage = storage_with("v1_graph.json", "graph_plain.json");
+    let graph = storage.load_universe("plain").unwrap();
+
+    assert_eq!(graph.cake_count(), 1);
+    assert_eq!(graph.node_count(), 2);
+    let login = node_by_title(&graph, "Login");
+    assert_eq!(login.parent_id.as_deref(), Some(node_by_title(&graph, "Product").id.as_str()));
+}
+
+#[test]
+fn migrates_unversioned_universe_file() {
+    let storage = storage_with("v1_universe.json", "roadmap.json");
+    let graph = storage.load_universe("roadmap").unwrap();
+    assert_eq!(graph.node_count(), 2);
+    assert_eq!(graph.relation_count(), 1);
+}
+
+#[derive(Deserialize)]
+struct Workspace {
+    graph: Graph,
+    viewport: Value,
+}
+
+#[test]
+fn migrates_unversioned_workspace_file() {
+    let storage = storage_with("v1_workspace.json", "workspaces/old.json");
+    let workspace: Workspace = storage.load_workspace("old").unwrap();
+    assert_eq!(workspace.graph.node_count(), 2);
+    assert_eq!(workspace.viewport["zoom"], 1.5);
+}
+
+#[test]
+fn loads_current_version_unchanged() {
+    let value = fixture("v2_universe.json");
+    assert_eq!(migrate(value.clone(), Path::new("v2_universe.json")).unwrap(), value);
+
+    let storage = storage_with("v2_universe.json", "release.json");
+    assert_eq!(storage.load_universe("release").unwrap().node_count(), 2);
+}
+
+#[test]
+fn rejects_newer_format_version() {
+    let mut value = fixture("v2_universe.json");
+    value["format_version"] = 99.into();
+    match migrate(value, Path::new("future.json")) {
+        Err(StorageError::VersionMismatch { found, supported, .. }) => {
+            assert_eq!(found, 99);
+            assert_eq!(supported, FORMAT_VERSION);
+        }
+        other => panic!("expected version mismatch, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn saved_files_carry_version_header() {
+    let storage = storage_with("v1_graph.json", "graph_plain.json");
+    let graph = storage.load_universe("plain").unwrap();
+    storage.save_graph(&graph, "plain").unwrap();
+
+    let saved: Value = serde_json::from_str(
+        &std::fs::read_to_string(storage.data_dir().join("graph_plain.json")).unwrap()
+    ).unwrap();
+    assert_eq!(saved["format_version"], FORMAT_VERSION);
+    assert!(saved["graph"]["nodes"].is_object());
+    assert_eq!(storage.load_universe("plain").unwrap().node_count(), 2);
+}
+
+#[test]
+fn universe_list_skips_unreadable_files_with_warning() {
+    let storage = storage_with("v1_universe.json", "roadmap.json");
+    std::fs::write(storage.data_dir().join("broken.json"), "{ not json").unwrap();
+
+    let (universes, warnings) = storage.get_universe_list().unwrap();
+    assert_eq!(universes.len(), 1);
+    assert!(matches!(warnings.as_slice(), [StorageError::Parse { path, .. }] if path.ends_with("broken.json")));
+}
+
+#[test]
+fn graph_file_without_cakes_loads_back_unchanged() {
+    let storage = storage_with("v2_universe.json", "release.json");
+    let mut graph = Graph::new();
+    let root = graph.create_node_3d("Loose".to_string(), NodeType::Concept, 1, 120.0, 0.5).unwrap();
+    graph.create_node_3d("Other".to_string(), NodeType::Concept, 1, 80.0, 1.5).unwrap();
+    storage.save_graph(&graph, "loose").unwrap();
+
+    let loaded = storage.load_universe("loose").unwrap();
+    assert_eq!(loaded.cake_count(), 0);
+    assert!(loaded.get_node(&root).unwrap().cake_id.is_none());
+    assert_eq!(serde_json::to_value(&loaded).unwrap(), serde_json::to_value(&graph).unwrap());
+}
+
+#[test]
+fn universe_listing_skips_unreadable_files_with_warning() {
+    let storage = storage_with("v2_universe.json", "release.json");
+    std::fs::write(storage.data_dir().join("graph_broken.json"), "{ \"graph\": ").unwrap();
+
+    let (universes, warnings) = storage.list_universes().unwrap();
+    assert_eq!(universes.iter().map(|u| u.id.as_str()).collect::<Vec<_>>(), ["release"]);
+    assert_eq!(warnings.len(), 1);
+    // 망가진 파일은 색인에 남지 않으므로 다시 읽을 때도 같은 경고
+    assert_eq!(storage.list_universes().unwrap().1.len(), 1);
+}
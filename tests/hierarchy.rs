@@ -0,0 +1,70 @@
+use std::path::Path;
+use cosmos::celestial::{Graph, GraphError, NodeType, RelationType, RelationTypeDef};
+
+fn fixture_graph(name: &str) -> Graph {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+    let value: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    serde_json::from_value(value["graph"].clone()).unwrap()
+}
+
+// 관계 (종류, 부모, 자식) 목록을 정렬해서
+fn hierarchy_links(graph: &Graph) -> Vec<(RelationType, String, String)> {
+    let mut links: Vec<_> = graph.get_relations()
+        .filter_map(|r| r.parent_and_child().map(|(p, c)| (r.relation_type.clone(), p.to_string(), c.to_string())))
+        .collect();
+    links.sort_by(|a, b| (&a.1, &a.2).cmp(&(&b.1, &b.2)));
+    links
+}
+
+#[test]
+fn repair_hierarchy_fixes_mismatched_parent_ids_children_and_relations() {
+    let mut graph = fixture_graph("v2_broken_hierarchy.json");
+    assert!(graph.repair_hierarchy() > 0);
+
+    let parent = |id: &str| graph.get_node(id).unwrap().parent_id.clone();
+    let children = |id: &str| graph.get_node(id).unwrap().children_ids.clone();
+    // 관계만 있던 부모는 parent_id로, 없는 부모를 가리키던 parent_id는 지움
+    assert_eq!(parent("billing").as_deref(), Some("root"));
+    assert_eq!(parent("login").as_deref(), Some("root"));
+    assert_eq!(parent("form").as_deref(), Some("login"));
+    assert_eq!(parent("orphan"), None);
+    // children_ids는 parent_id에서 다시 만들어짐
+    assert_eq!(children("root"), ["billing", "login"]);
+    assert_eq!(children("login"), ["form"]);
+    assert!(children("billing").is_empty());
+    // 두 번째 부모(Child 관계)는 지우고, parent_id만 있던 연결에는 Hierarchy 관계를 채움
+    assert_eq!(hierarchy_links(&graph), vec![
+        (RelationType::Hierarchy, "login".to_string(), "form".to_string()),
+        (RelationType::Parent, "root".to_string(), "billing".to_string()),
+        (RelationType::Hierarchy, "root".to_string(), "login".to_string()),
+    ]);
+    assert!(graph.validate().is_empty());
+
+    // 이미 고친 그래프는 그대로
+    assert_eq!(graph.repair_hierarchy(), 0);
+}
+
+#[test]
+fn set_parent_keeps_old_parent_when_new_link_is_rejected() {
+    let mut graph = Graph::new();
+    let root = graph.create_node_3d("Root".to_string(), NodeType::Root, 0, 0.0, 0.0).unwrap();
+    let first = graph.create_node_3d("First".to_string(), NodeType::Concept, 1, 100.0, 0.0).unwrap();
+    let second = graph.create_node_3d("Second".to_string(), NodeType::Concept, 1, 100.0, 1.0).unwrap();
+    let task = graph.create_node_3d("Task".to_string(), NodeType::Task, 2, 200.0, 0.0).unwrap();
+    graph.set_parent(&first, Some(&root)).unwrap();
+    graph.set_parent(&second, Some(&root)).unwrap();
+    graph.set_parent(&task, Some(&first)).unwrap();
+    let before = serde_json::to_value(&graph).unwrap();
+
+    // 계층 규칙은 통과하지만 Hierarchy 관계 타입이 Root에서 나가는 것만 허용
+    let hierarchy = graph.relation_types().get(&RelationType::Hierarchy).unwrap().clone();
+    graph.register_relation_type(RelationTypeDef { allowed_sources: vec!["Root".to_string()], ..hierarchy }).unwrap();
+    let before_rules = serde_json::to_value(&graph).unwrap();
+    assert_ne!(before, before_rules);
+
+    let result = graph.set_parent(&task, Some(&second));
+    assert!(matches!(result, Err(GraphError::RelationNotAllowed { .. })));
+    assert_eq!(serde_json::to_value(&graph).unwrap(), before_rules);
+    assert_eq!(graph.get_parent(&task).unwrap().id, first);
+    assert_eq!(graph.get_children(&first).len(), 1);
+}
@@ -0,0 +1,140 @@
+use cosmos::celestial::{Cake, EditHistory, Graph, GraphError, NodeType, Position2D, RelationType};
+use serde_json::Value;
+
+fn snapshot(graph: &Graph) -> Value {
+    serde_json::to_value(graph).unwrap()
+}
+
+#[test]
+fn undo_and_redo_a_delete_with_relations() {
+    let mut graph = Graph::new();
+    let mut history = EditHistory::new();
+    let empty = snapshot(&graph);
+
+    let (star, planet) = history.apply(&mut graph, "Create", |graph| {
+        let star = graph.create_node_3d("Star".to_string(), NodeType::Star, 0, 100.0, 0.0)?;
+        let planet = graph.create_node_3d("Planet".to_string(), NodeType::Planet, 1, 150.0, 1.0)?;
+        graph.add_relation(&star, &planet, RelationType::Orbit)?;
+        graph.add_relation(&planet, &star, RelationType::Reference)?;
+        Ok((star, planet))
+    }).unwrap();
+    let created = snapshot(&graph);
+
+    history.apply(&mut graph, "Delete", |graph| graph.remove_node(&star)).unwrap();
+    let deleted = snapshot(&graph);
+    assert_eq!(graph.relation_count(), 0);
+
+    assert_eq!(history.undo(&mut graph).as_deref(), Some("Delete"));
+    assert_eq!(snapshot(&graph), created);
+    // 되돌린 관계는 인덱스에도 다시 들어간다
+    assert_eq!(graph.outgoing_relations(&star).count(), 1);
+    assert_eq!(graph.incoming_relations(&star).count(), 1);
+
+    assert_eq!(history.redo(&mut graph).as_deref(), Some("Delete"));
+    assert_eq!(snapshot(&graph), deleted);
+    assert!(graph.node_relations(&planet).next().is_none());
+
+    history.undo(&mut graph);
+    history.undo(&mut graph);
+    assert_eq!(snapshot(&graph), empty);
+    assert!(!history.can_undo());
+
+    history.redo(&mut graph);
+    assert_eq!(snapshot(&graph), created);
+    assert_eq!(history.redo_label(), Some("Delete"));
+}
+
+#[test]
+fn undoing_a_child_restores_the_parents_children() {
+    let mut graph = Graph::new();
+    let mut history = EditHistory::new();
+    let parent = graph.create_node_3d("Root".to_string(), NodeType::Root, 0, 100.0, 0.0).unwrap();
+    let before = snapshot(&graph);
+
+    let child = history.apply(&mut graph, "Add child", |graph| {
+        graph.create_child_node("Idea".to_string(), NodeType::Concept, &parent, Position2D::new(10.0, 0.0))
+    }).unwrap();
+    let after = snapshot(&graph);
+    assert_eq!(graph.get_node(&parent).unwrap().children_ids, std::slice::from_ref(&child));
+
+    history.undo(&mut graph);
+    assert_eq!(snapshot(&graph), before);
+    assert!(graph.get_node(&parent).unwrap().children_ids.is_empty());
+    assert!(graph.get_node(&child).is_none());
+
+    history.redo(&mut graph);
+    assert_eq!(snapshot(&graph), after);
+    assert_eq!(graph.get_parent(&child).unwrap().id, parent);
+}
+
+#[test]
+fn a_grouped_drag_is_one_step() {
+    let mut graph = Graph::new();
+    let mut history = EditHistory::new();
+    let cake = graph.create_cake("Plan".to_string(), Position2D::new(0.0, 0.0));
+    graph.create_cake_node("Root".to_string(), NodeType::Root, &cake, 0, Cake::node_radius(0), 0.0).unwrap();
+    let before = snapshot(&graph);
+
+    // 드래그하는 동안 여러 프레임에 걸쳐 움직인다
+    history.begin_group(&mut graph, "Move cake");
+    for _ in 0..5 {
+        let mut moving = graph.get_cake_mut(&cake).unwrap();
+        moving.center.x += 10.0;
+        moving.center.y -= 4.0;
+    }
+    history.end_group(&mut graph);
+    let moved = snapshot(&graph);
+
+    assert_eq!(history.undo_label(), Some("Move cake"));
+    assert_eq!(history.undo(&mut graph).as_deref(), Some("Move cake"));
+    assert_eq!(snapshot(&graph), before);
+    assert!(!history.can_undo());
+
+    history.redo(&mut graph);
+    assert_eq!(snapshot(&graph), moved);
+    let center = graph.get_cake(&cake).unwrap().center;
+    assert_eq!((center.x, center.y), (50.0, -20.0));
+}
+
+#[test]
+fn a_new_edit_after_undo_drops_the_redo_step() {
+    let mut graph = Graph::new();
+    let mut history = EditHistory::new();
+    let node = graph.create_node_3d("Draft".to_string(), NodeType::Note, 3, 100.0, 0.0).unwrap();
+
+    history.apply(&mut graph, "Rename", |graph| {
+        graph.get_node_mut(&node).ok_or(GraphError::UnknownNode(node.clone()))?.title = "Final".to_string();
+        Ok(())
+    }).unwrap();
+    history.undo(&mut graph);
+    assert_eq!(graph.get_node(&node).unwrap().title, "Draft");
+    assert!(history.can_redo());
+
+    history.apply(&mut graph, "Tag", |graph| graph.set_tags(&node, ["todo"])).unwrap();
+    assert!(!history.can_redo());
+    assert_eq!(history.redo(&mut graph), None);
+    assert_eq!(graph.get_node(&node).unwrap().title, "Draft");
+}
+
+#[test]
+fn a_failed_apply_rolls_back_its_partial_changes() {
+    let mut graph = Graph::new();
+    let mut history = EditHistory::new();
+    let star = graph.create_node_3d("Star".to_string(), NodeType::Star, 0, 100.0, 0.0).unwrap();
+    let before = snapshot(&graph);
+
+    let result: Result<(), _> = history.apply(&mut graph, "Broken", |graph| {
+        graph.get_node_mut(&star).unwrap().title = "Renamed".to_string();
+        let planet = graph.create_node_3d("Planet".to_string(), NodeType::Planet, 1, 150.0, 0.0)?;
+        graph.add_relation(&star, &planet, RelationType::Orbit)?;
+        graph.remove_node(&star)?;
+        graph.add_relation(&planet, "missing", RelationType::Reference)?;
+        Ok(())
+    });
+
+    assert!(result.is_err());
+    assert_eq!(snapshot(&graph), before);
+    assert_eq!(graph.node_count(), 1);
+    assert!(graph.node_relations(&star).next().is_none());
+    assert!(!history.can_undo());
+}
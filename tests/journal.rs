@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::PathBuf;
+use cosmos::celestial::{Graph, Journal, NodeType, Position2D, PropertyValue, RelationType};
+use cosmos::storage::Storage;
+use serde_json::Value;
+
+fn temp_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("cosmos-test-{}", uuid::Uuid::new_v4()))
+}
+
+fn temp_storage() -> Storage {
+    Storage::with_dir(temp_dir()).unwrap()
+}
+
+fn snapshot(graph: &Graph) -> Value {
+    serde_json::to_value(graph).unwrap()
+}
+
+// 워크스페이스 파일에 저널을 다시 적용한 그래프
+fn reopen(storage: &Storage, id: &str) -> Graph {
+    let mut graph: Graph = storage.load_workspace(id).unwrap();
+    graph.replay(&storage.load_journal(id).unwrap().0);
+    graph
+}
+
+#[test]
+fn replaying_the_journal_onto_the_snapshot_restores_the_graph() {
+    let storage = temp_storage();
+    let mut graph = Graph::new();
+    let mut journal = Journal::attach(&mut graph);
+    let cake = graph.create_cake("Plan".to_string(), Position2D::new(0.0, 0.0));
+    let star = graph.create_node_3d("Star".to_string(), NodeType::Star, 0, 100.0, 0.0).unwrap();
+    journal.collect(&mut graph);
+    storage.save_workspace(&graph, "plan").unwrap();
+
+    // 스냅샷 이후의 변경은 저널에만 남는다
+    let planet = graph.create_child_node("Planet".to_string(), NodeType::Planet, &star, Position2D::new(50.0, 0.0)).unwrap();
+    let note = graph.create_node_3d("Note".to_string(), NodeType::Note, 3, 100.0, 1.0).unwrap();
+    let reference = graph.add_relation(&planet, &note, RelationType::Reference).unwrap();
+    graph.get_node_mut(&star).unwrap().title = "Sun".to_string();
+    graph.set_relation_property(&reference, "page", PropertyValue::Number(3.0)).unwrap();
+    graph.get_cake_mut(&cake).unwrap().center.x = 40.0;
+    storage.append_journal("plan", &journal.collect(&mut graph)).unwrap();
+    graph.remove_node(&note).unwrap();
+    graph.set_tags(&planet, ["core"]).unwrap();
+    storage.append_journal("plan", &journal.collect(&mut graph)).unwrap();
+
+    let reopened = reopen(&storage, "plan");
+    assert_eq!(snapshot(&reopened), snapshot(&graph));
+    assert_eq!(reopened.nodes_with_tag("core").len(), 1);
+    assert!(reopened.node_relations(&planet).all(|r| r.target_id != note));
+}
+
+#[test]
+fn compaction_moves_the_journal_into_the_archive_once() {
+    let storage = temp_storage();
+    let mut graph = Graph::new();
+    let mut journal = Journal::attach(&mut graph);
+    storage.save_workspace(&graph, "plan").unwrap();
+
+    let star = graph.create_node_3d("Star".to_string(), NodeType::Star, 0, 100.0, 0.0).unwrap();
+    let first = journal.collect(&mut graph);
+    storage.append_journal("plan", &first).unwrap();
+    storage.save_workspace(&graph, "plan").unwrap();
+    storage.compact_journal("plan").unwrap();
+    assert!(storage.load_journal("plan").unwrap().0.is_empty());
+    assert_eq!(snapshot(&reopen(&storage, "plan")), snapshot(&graph));
+
+    // 예전 기록을 쓰고 저널을 지우기 전에 멈췄던 것처럼 저널을 되살려도 항목이 두 번 남지 않는다
+    storage.append_journal("plan", &first).unwrap();
+    storage.compact_journal("plan").unwrap();
+    assert_eq!(storage.load_journal_history("plan").unwrap().0.len(), first.len());
+
+    graph.get_node_mut(&star).unwrap().title = "Sun".to_string();
+    let second = journal.collect(&mut graph);
+    storage.append_journal("plan", &second).unwrap();
+    assert_eq!(snapshot(&reopen(&storage, "plan")), snapshot(&graph));
+    storage.save_workspace(&graph, "plan").unwrap();
+    storage.compact_journal("plan").unwrap();
+
+    let (history, warnings) = storage.load_journal_history("plan").unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(history.len(), first.len() + second.len());
+    assert_eq!(history.last().unwrap().op.summary(), second.last().unwrap().op.summary());
+    assert_eq!(snapshot(&reopen(&storage, "plan")), snapshot(&graph));
+}
+
+#[test]
+fn appending_after_a_torn_line_starts_a_new_line() {
+    let dir = temp_dir();
+    let storage = Storage::with_dir(dir.clone()).unwrap();
+    let mut graph = Graph::new();
+    let mut journal = Journal::attach(&mut graph);
+    storage.save_workspace(&graph, "plan").unwrap();
+
+    graph.create_node_3d("Star".to_string(), NodeType::Star, 0, 100.0, 0.0).unwrap();
+    storage.append_journal("plan", &journal.collect(&mut graph)).unwrap();
+    let path = dir.join("workspaces/plan.journal.jsonl");
+    let mut content = fs::read(&path).unwrap();
+    content.extend_from_slice(b"{\"at\":");
+    fs::write(&path, content).unwrap();
+
+    graph.create_node_3d("Planet".to_string(), NodeType::Planet, 1, 150.0, 0.0).unwrap();
+    storage.append_journal("plan", &journal.collect(&mut graph)).unwrap();
+    // 끊긴 줄만 건너뛰고 알린다
+    let (entries, warnings) = storage.load_journal("plan").unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(snapshot(&reopen(&storage, "plan")), snapshot(&graph));
+}
@@ -0,0 +1,41 @@
+use std::path::Path;
+use cosmos::celestial::{Graph, GraphError, NodeType, RelationType, UniverseSchema};
+
+fn agile_graph() -> Graph {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas/agile.json");
+    Graph::with_schema(&UniverseSchema::load(path).unwrap()).unwrap()
+}
+
+#[test]
+fn agile_schema_rejects_type_on_disallowed_layer() {
+    let mut graph = agile_graph();
+    let goal = NodeType::from_name("Goal");
+
+    let result = graph.create_node_3d("Ship v2".to_string(), goal.clone(), 2, 100.0, 0.0);
+    assert_eq!(result, Err(GraphError::LayerNotAllowed { node_type: goal.clone(), layer: 2 }));
+    assert_eq!(graph.node_count(), 0);
+
+    assert!(graph.create_node_3d("Ship v2".to_string(), goal, 0, 100.0, 0.0).is_ok());
+}
+
+#[test]
+fn agile_schema_does_not_fall_back_to_builtin_types() {
+    let graph = agile_graph();
+    assert!(graph.node_types().get(&NodeType::Note).is_some());
+    assert!(graph.node_types().get(&NodeType::Root).is_none());
+    assert!(!graph.allows_on_layer(&NodeType::Root, 0));
+}
+
+#[test]
+fn agile_schema_rejects_undeclared_relation_type() {
+    let mut graph = agile_graph();
+    let story = graph.create_node_3d("Login".to_string(), NodeType::from_name("Story"), 2, 100.0, 0.0).unwrap();
+    let note = graph.create_node_3d("Notes".to_string(), NodeType::Note, 3, 100.0, 0.0).unwrap();
+
+    assert!(graph.relation_types().get(&RelationType::Orbit).is_none());
+    let result = graph.add_relation(&story, &note, RelationType::Orbit);
+    assert_eq!(result, Err(GraphError::InvalidRelationType("Orbit".to_string())));
+    assert_eq!(graph.relation_count(), 0);
+
+    assert!(graph.add_relation(&story, &note, RelationType::from_name("Relates")).is_ok());
+}
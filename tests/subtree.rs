@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use cosmos::celestial::{Cake, DeletePolicy, Graph, GraphError, NodeType, Position2D, RelationType, RelationTypeDef};
+
+// 케이크 안의 Root → Concept → Task, 그리고 서브트리 안/밖을 잇는 Reference 관계
+struct Tree {
+    graph: Graph,
+    root: String,
+    concept: String,
+    task: String,
+    outside: String,
+}
+
+fn tree() -> Tree {
+    let mut graph = Graph::new();
+    let cake = graph.create_cake("Plan".to_string(), Position2D::new(0.0, 0.0));
+    let root = graph.create_cake_node("Root".to_string(), NodeType::Root, &cake, 0, Cake::node_radius(0), 0.0).unwrap();
+    let concept = graph.create_cake_node("Concept".to_string(), NodeType::Concept, &cake, 1, Cake::node_radius(1), 1.0).unwrap();
+    let task = graph.create_cake_node("Task".to_string(), NodeType::Task, &cake, 2, Cake::node_radius(2), 2.0).unwrap();
+    let outside = graph.create_node_3d("Outside".to_string(), NodeType::Note, 3, 100.0, 0.0).unwrap();
+    graph.set_parent(&concept, Some(&root)).unwrap();
+    graph.set_parent(&task, Some(&concept)).unwrap();
+    graph.add_relation(&concept, &task, RelationType::Reference).unwrap();
+    graph.add_relation(&task, &outside, RelationType::Reference).unwrap();
+    Tree { graph, root, concept, task, outside }
+}
+
+#[test]
+fn duplicate_remaps_internal_relations_to_the_copies() {
+    let Tree { mut graph, concept, task, outside, .. } = tree();
+    let original_ids: HashSet<String> = graph.get_relations().map(|r| r.id.clone()).collect();
+    let originals: HashSet<String> = [concept.clone(), task.clone(), outside.clone()].into();
+
+    let copy_root = graph.duplicate_subtree(&concept, None).unwrap();
+    let copy_task = graph.get_node(&copy_root).unwrap().children_ids[0].clone();
+    assert!(!originals.contains(&copy_task));
+
+    let copied: Vec<_> = graph.get_relations().filter(|r| !original_ids.contains(&r.id)).collect();
+    let mut kinds: Vec<_> = copied.iter().map(|r| r.relation_type.clone()).collect();
+    kinds.sort_by_key(|t| t.name().to_string());
+    assert_eq!(kinds, [RelationType::Hierarchy, RelationType::Reference]);
+    for relation in &copied {
+        assert_eq!((relation.source_id.as_str(), relation.target_id.as_str()), (copy_root.as_str(), copy_task.as_str()));
+    }
+    // 바깥 노드와의 관계는 복사하지 않고, 원본을 가리키는 복사본 관계도 없다
+    assert!(graph.node_relations(&copy_task).all(|r| !originals.contains(&r.source_id) && !originals.contains(&r.target_id)));
+    assert_eq!(graph.relation_count(), original_ids.len() + 2);
+    assert_eq!(graph.get_node(&copy_root).unwrap().parent_id, None);
+}
+
+#[test]
+fn promote_to_grandparent_changes_nothing_when_a_child_is_rejected() {
+    let Tree { mut graph, concept, .. } = tree();
+    let before = serde_json::to_value(&graph).unwrap();
+
+    // Root는 Task를 자식으로 가질 수 없다
+    let result = graph.remove_node_with_policy(&concept, DeletePolicy::PromoteToGrandparent);
+    assert!(matches!(result, Err(GraphError::InvalidHierarchy { .. })));
+    assert_eq!(serde_json::to_value(&graph).unwrap(), before);
+}
+
+#[test]
+fn promote_to_grandparent_rolls_back_moved_children_on_a_late_failure() {
+    let Tree { mut graph, root, concept, task, .. } = tree();
+    let note = graph.create_node_3d("Note".to_string(), NodeType::Note, 2, 100.0, 0.0).unwrap();
+    graph.set_parent(&note, Some(&concept)).unwrap();
+    // 계층 규칙은 통과하지만 Note를 잇는 Hierarchy 관계는 새로 만들 수 없다
+    graph.allow_child_type(&NodeType::Root, &NodeType::Task).unwrap();
+    graph.allow_child_type(&NodeType::Root, &NodeType::Note).unwrap();
+    let hierarchy = graph.relation_types().get(&RelationType::Hierarchy).unwrap().clone();
+    graph.register_relation_type(RelationTypeDef {
+        allowed_targets: vec!["Concept".to_string(), "Task".to_string()],
+        ..hierarchy
+    }).unwrap();
+    let before = serde_json::to_value(&graph).unwrap();
+
+    let result = graph.remove_node_with_policy(&concept, DeletePolicy::PromoteToGrandparent);
+    assert!(matches!(result, Err(GraphError::RelationNotAllowed { .. })));
+    assert_eq!(serde_json::to_value(&graph).unwrap(), before);
+    assert_eq!(graph.get_parent(&task).unwrap().id, concept);
+    assert_eq!(graph.get_parent(&concept).unwrap().id, root);
+}
+
+#[test]
+fn cascade_removes_every_descendant_relation_from_the_index() {
+    let Tree { mut graph, root, concept, task, outside } = tree();
+
+    let removed = graph.remove_node_with_policy(&concept, DeletePolicy::Cascade).unwrap();
+    let removed_ids: Vec<&str> = removed.iter().map(|n| n.id.as_str()).collect();
+    assert_eq!(removed_ids, [concept.as_str(), task.as_str()]);
+
+    assert_eq!(graph.relation_count(), 0);
+    for id in [&root, &outside] {
+        assert!(graph.outgoing_relations(id).next().is_none());
+        assert!(graph.incoming_relations(id).next().is_none());
+        assert!(graph.node_relations(id).next().is_none());
+    }
+    assert!(graph.incoming_relations_of_type(&outside, &RelationType::Reference).next().is_none());
+    assert!(graph.get_node(&root).unwrap().children_ids.is_empty());
+}
+
+#[test]
+fn moving_into_another_cake_uses_the_new_layer_radius() {
+    let Tree { mut graph, concept, task, .. } = tree();
+    let other = graph.create_cake("Other".to_string(), Position2D::new(500.0, 0.0));
+    let host = graph.create_cake_node("Host".to_string(), NodeType::Root, &other, 0, Cake::node_radius(0), 0.0).unwrap();
+    let angle = graph.get_node(&task).unwrap().layer_angle;
+
+    graph.move_subtree(&concept, Some(&host)).unwrap();
+    let task = graph.get_node(&task).unwrap();
+    assert_eq!(task.cake_id.as_deref(), Some(other.as_str()));
+    assert_eq!(task.layer, 2);
+    assert_eq!((task.layer_radius, task.layer_angle), (Cake::node_radius(2), angle));
+
+    // 최상위로 옮기면 기본 층으로
+    graph.move_subtree(&concept, None).unwrap();
+    let concept = graph.get_node(&concept).unwrap();
+    assert_eq!((concept.layer, concept.layer_radius), (1, Cake::node_radius(1)));
+}
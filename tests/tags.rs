@@ -0,0 +1,54 @@
+use cosmos::celestial::{Graph, NodeType};
+
+fn tagged(tags: &[&[&str]]) -> (Graph, Vec<String>) {
+    let mut graph = Graph::new();
+    let ids = tags.iter()
+        .enumerate()
+        .map(|(i, node_tags)| {
+            let id = graph.create_node_3d(format!("Node {}", i), NodeType::Concept, 1, 100.0, 0.0).unwrap();
+            graph.set_tags(&id, node_tags.iter().copied()).unwrap();
+            id
+        })
+        .collect();
+    (graph, ids)
+}
+
+fn tagged_ids(graph: &Graph, tag: &str) -> Vec<String> {
+    let mut ids: Vec<String> = graph.nodes_with_tag(tag).into_iter().map(|n| n.id.clone()).collect();
+    ids.sort();
+    ids
+}
+
+fn sorted(ids: &[&String]) -> Vec<String> {
+    let mut ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn prefix_query_matches_subtags_but_not_longer_names() {
+    let (graph, ids) = tagged(&[&["area"], &["area/backend"], &["area/backend/db"], &["areafoo"], &["area-x"]]);
+
+    assert_eq!(tagged_ids(&graph, "area"), sorted(&[&ids[0], &ids[1], &ids[2]]));
+    assert_eq!(tagged_ids(&graph, "/area/backend/ "), sorted(&[&ids[1], &ids[2]]));
+    assert_eq!(tagged_ids(&graph, "areafoo"), sorted(&[&ids[3]]));
+    assert!(tagged_ids(&graph, "are").is_empty());
+    assert!(graph.node_has_tag(&ids[2], "area/backend"));
+    assert!(!graph.node_has_tag(&ids[3], "area"));
+}
+
+#[test]
+fn rename_moves_subtags_and_merge_combines_tags() {
+    let (mut graph, ids) = tagged(&[&["area/backend", "team"], &["area/backend/db"], &["areafoo"], &["server"]]);
+
+    assert_eq!(graph.rename_tag("area/backend", "area/server"), 2);
+    assert_eq!(graph.all_tags(), [("area/server", 1), ("area/server/db", 1), ("areafoo", 1), ("server", 1), ("team", 1)]);
+    assert!(graph.get_node(&ids[0]).unwrap().tags.contains("team"));
+    assert!(tagged_ids(&graph, "area/backend").is_empty());
+
+    // 겹치는 태그는 하나로 합쳐진다
+    assert_eq!(graph.merge_tags(&["server", "area/server"], "backend"), 3);
+    assert_eq!(graph.all_tags(), [("areafoo", 1), ("backend", 2), ("backend/db", 1), ("team", 1)]);
+    assert_eq!(tagged_ids(&graph, "backend"), sorted(&[&ids[0], &ids[1], &ids[3]]));
+    assert_eq!(tagged_ids(&graph, "areafoo"), sorted(&[&ids[2]]));
+}
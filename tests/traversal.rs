@@ -0,0 +1,57 @@
+use cosmos::celestial::{Direction, Graph, NodeType, Relation, RelationType, TraversalFilter};
+
+// A →(10) B →(10) D, A →(1) C ⇢(1, Association) E →(1) D
+fn weighted_graph() -> (Graph, [String; 5]) {
+    let mut graph = Graph::new();
+    let ids = ["A", "B", "C", "D", "E"]
+        .map(|title| graph.create_node_3d(title.to_string(), NodeType::Concept, 1, 100.0, 0.0).unwrap());
+    let [a, b, c, d, e] = &ids;
+    for (source, target, relation_type, weight) in [
+        (a, b, RelationType::Reference, 10.0),
+        (b, d, RelationType::Reference, 10.0),
+        (a, c, RelationType::Reference, 1.0),
+        (c, e, RelationType::Association, 1.0),
+        (e, d, RelationType::Reference, 1.0),
+    ] {
+        graph.add_relation_entry(Relation::new(source.clone(), target.clone(), relation_type).with_weight(weight)).unwrap();
+    }
+    (graph, ids)
+}
+
+#[test]
+fn unweighted_path_takes_fewest_relations_and_weighted_path_lowest_cost() {
+    let (graph, [a, b, c, d, e]) = weighted_graph();
+    let outgoing = TraversalFilter::new(Direction::Outgoing);
+
+    let fewest = graph.shortest_path(&a, &d, &outgoing).unwrap();
+    assert_eq!(fewest.node_ids, [a.clone(), b.clone(), d.clone()]);
+    assert_eq!((fewest.len(), fewest.cost), (2, 2.0));
+
+    let cheapest = graph.weighted_shortest_path(&a, &d, &outgoing).unwrap();
+    assert_eq!(cheapest.node_ids, [a.clone(), c.clone(), e.clone(), d.clone()]);
+    assert_eq!(cheapest.cost, 3.0);
+    assert_eq!(cheapest.relation_ids.len(), 3);
+
+    // Association을 빼면 비싼 길로
+    let references = TraversalFilter::new(Direction::Outgoing).with_types([RelationType::Reference]);
+    let cheapest = graph.weighted_shortest_path(&a, &d, &references).unwrap();
+    assert_eq!(cheapest.node_ids, [a.clone(), b.clone(), d.clone()]);
+    assert_eq!(cheapest.cost, 20.0);
+}
+
+#[test]
+fn path_direction_follows_filter() {
+    let (graph, [a, b, _, d, _]) = weighted_graph();
+
+    assert!(graph.shortest_path(&d, &a, &TraversalFilter::new(Direction::Outgoing)).is_none());
+    assert!(graph.weighted_shortest_path(&d, &a, &TraversalFilter::new(Direction::Outgoing)).is_none());
+
+    let backwards = graph.shortest_path(&d, &a, &TraversalFilter::new(Direction::Incoming)).unwrap();
+    assert_eq!(backwards.node_ids, [d.clone(), b.clone(), a.clone()]);
+    let either = graph.weighted_shortest_path(&d, &a, &TraversalFilter::new(Direction::Both)).unwrap();
+    assert_eq!(either.cost, 3.0);
+
+    assert!(graph.shortest_path(&a, &d, &TraversalFilter::new(Direction::Incoming)).is_none());
+    let same = graph.shortest_path(&a, &a, &TraversalFilter::default()).unwrap();
+    assert!(same.is_empty());
+}